@@ -1,5 +1,3 @@
-use std::env;
-
 use yt_api::{
 	search::{Error, ItemType, SearchList, VideoLocation},
 	ApiKey,
@@ -8,8 +6,8 @@ use yt_api::{
 /// prints the first answer of a search query
 fn main() -> Result<(), Error> {
 	futures::executor::block_on(async {
-		// take api key from enviroment variable
-		let key = ApiKey::new(&env::var("YT_API_KEY").expect("YT_API_KEY env-var not found"));
+		// take api key from the YT_API_KEY environment variable
+		let key = ApiKey::from_env()?;
 
 		// create the SearchList struct for the query "rust lang"
 		let result = SearchList::new(key)
@@ -18,7 +16,7 @@ fn main() -> Result<(), Error> {
 			.item_type(ItemType::Video)
 			.location(VideoLocation::new(40.73061, -73.93524))
 			.location_radius("100km")
-			.video_embeddable()
+			.video_embeddable(true)
 			.await?;
 
 		// outputs the video_id of the first search result
@@ -27,8 +25,8 @@ fn main() -> Result<(), Error> {
 			result.items[0].snippet.title.as_ref().unwrap()
 		);
 		println!(
-			"https://youtube.com/watch?v={}",
-			result.items[0].id.video_id.as_ref().unwrap()
+			"{}",
+			result.items[0].id.video_id.as_ref().unwrap().watch_url()
 		);
 
 		Ok(())
@@ -1,5 +1,3 @@
-use std::env;
-
 use yt_api::{
 	playlistitems::{Error, PlaylistItems},
 	ApiKey,
@@ -8,8 +6,8 @@ use yt_api::{
 /// prints the first answer of a search query
 fn main() -> Result<(), Error> {
 	futures::executor::block_on(async {
-		// take api key from enviroment variable
-		let key = ApiKey::new(&env::var("YT_API_KEY").expect("YT_API_KEY env-var not found"));
+		// take api key from the YT_API_KEY environment variable
+		let key = ApiKey::from_env()?;
 
 		// create the PlaylistItems struct for some playlist ID
 		let result = PlaylistItems::new(key)
@@ -18,10 +16,7 @@ fn main() -> Result<(), Error> {
 			.await?;
 
 		for item in result.items {
-			println!(
-				"https://youtube.com/watch?v={}",
-				item.snippet.resource_id.video_id
-			);
+			println!("{}", item.snippet.resource_id.video_id.watch_url());
 		}
 
 		Ok(())
@@ -1,5 +1,3 @@
-use std::env;
-
 use yt_api::{
 	search::{Error, ItemType, SearchList},
 	ApiKey,
@@ -8,8 +6,8 @@ use yt_api::{
 /// prints the first answer of a search query
 fn main() -> Result<(), Error> {
 	futures::executor::block_on(async {
-		// take api key from enviroment variable
-		let key = ApiKey::new(&env::var("YT_API_KEY").expect("YT_API_KEY env-var not found"));
+		// take api key from the YT_API_KEY environment variable
+		let key = ApiKey::from_env()?;
 
 		// create the SearchList struct for the query "rust lang"
 		let result = SearchList::new(key)
@@ -24,8 +22,8 @@ fn main() -> Result<(), Error> {
 		);
 		// outputs the video id of the first search result
 		println!(
-			"https://youtube.com/watch?v={}",
-			result.items[0].id.video_id.as_ref().unwrap()
+			"{}",
+			result.items[0].id.video_id.as_ref().unwrap().watch_url()
 		);
 
 		println!(
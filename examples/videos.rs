@@ -1,32 +1,34 @@
-use std::env;
+use yt_api::{
+	videos::{Error, Videos},
+	ApiKey,
+};
 
-use yt_api::{ApiKey, videos::{Error, Videos}};
-
-/// prints the first answer of a search query
+/// prints the title and thumbnail of a video
 fn main() -> Result<(), Error> {
 	futures::executor::block_on(async {
-		// take api key from enviroment variable
-		let key = ApiKey::new(&env::var("YT_API_KEY").expect("YT_API_KEY env-var not found"));
+		// take api key from the YT_API_KEY environment variable
+		let key = ApiKey::from_env()?;
 
-		// create the SearchList struct for the query "rust lang"
-		let result = Videos::new(key)
-			.id("DnJgoWDxG2A")
-			.await?;
+		// look up the video with some video ID
+		let video = Videos::get_one(key, "DnJgoWDxG2A").await?;
 
-		// outputs the title of the first search result
-		println!(
-			"Title: \"{}\"",
-			result.items[0].snippet.title.as_ref().unwrap()
-		);
-		// outputs the video id of the first search result
-		println!(
-			"https://youtube.com/watch?v={}",
-			result.items[0].id
-		);
+		// deleted or private videos are not found
+		let video = match video {
+			Some(video) => video,
+			None => {
+				println!("video not found");
+				return Ok(());
+			}
+		};
+
+		// outputs the title of the video
+		println!("Title: \"{}\"", video.snippet.title.as_ref().unwrap());
+		// outputs the link to the video
+		println!("{}", video.watch_url());
 
 		println!(
 			"Default thumbnail: {}",
-			result.items[0]
+			video
 				.snippet
 				.thumbnails
 				.as_ref()
@@ -0,0 +1,33 @@
+//! searches from within a browser, build it with
+//!
+//! ```sh
+//! cargo build --example wasm --target wasm32-unknown-unknown --no-default-features --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/debug/examples/wasm.wasm
+//! ```
+//!
+//! and load `pkg/wasm.js` from a page served over http. The api key has to
+//! allow requests from the origin of the page, see the restrictions of the
+//! key in the google cloud console.
+
+/// logs the title of the first answer of a search query to the browser console
+#[cfg(target_arch = "wasm32")]
+fn main() {
+	use yt_api::{ApiKey, Client};
+
+	wasm_bindgen_futures::spawn_local(async {
+		let client = Client::new(ApiKey::new("your-youtube-api-key"));
+		let message = match client.search().q("rust lang").await {
+			Ok(result) => format!(
+				"Title: \"{}\"",
+				result.items[0].snippet.title.as_deref().unwrap_or_default()
+			),
+			Err(error) => error.to_string(),
+		};
+		web_sys::console::log_1(&message.into());
+	});
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+	eprintln!("build this example for wasm32-unknown-unknown with the wasm feature");
+}
@@ -0,0 +1,181 @@
+//! synchronous api for callers without an async runtime, e.g. scripts or gui
+//! applications
+//!
+//! Every call drives the request of the async [`Client`](../struct.Client.html)
+//! to completion on the current thread with
+//! [`futures::executor::block_on`](https://docs.rs/futures/0.3/futures/executor/fn.block_on.html),
+//! or with the `reqwest` feature on a current thread tokio runtime owned by
+//! the blocking client, since the requests of reqwest need one.
+//!
+//! The blocking client must not be used within an async context. Inside a
+//! future run by a `futures` executor every call panics, and with the
+//! `reqwest` feature inside a tokio runtime as well. Inside other runtimes
+//! it blocks a worker thread and can deadlock, use the async client there
+//! instead.
+//!
+//! ```rust,no_run
+//! # use yt_api::ApiKey;
+//! let client = yt_api::blocking::Client::new(ApiKey::new("your-youtube-api-key"));
+//!
+//! let response = client.videos(vec!["DnJgoWDxG2A"]).unwrap();
+//! println!("{:?}", response.items[0].snippet.title);
+//!
+//! // any other request is sent with `send`
+//! let request = client.as_async().videos().id("DnJgoWDxG2A").statistics();
+//! let response = client.send(request).unwrap();
+//! println!("{:?}", response.items[0].statistics);
+//! ```
+//!
+//! ```rust,should_panic
+//! # use yt_api::ApiKey;
+//! let client = yt_api::blocking::Client::new(ApiKey::new("your-youtube-api-key"));
+//!
+//! futures::executor::block_on(async {
+//!     // panics instead of deadlocking
+//!     let _ = client.search("rust lang");
+//! });
+//! ```
+
+use std::future::IntoFuture;
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+use std::sync::Arc;
+
+#[cfg(any(feature = "surf", feature = "reqwest"))]
+use super::ApiKey;
+use super::{channels, playlistitems, search, videos, ChannelId, Error, PlaylistId, VideoId};
+
+/// blocking wrapper of an async [`Client`](../struct.Client.html), sharing
+/// its settings
+#[derive(Debug, Clone)]
+pub struct Client {
+	client: super::Client,
+	/// runtime of the requests of reqwest, or why it could not be created,
+	/// shared by the clones so the connections of reqwest stay usable
+	#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+	runtime: Result<Arc<tokio::runtime::Runtime>, String>,
+}
+
+impl Client {
+	/// create client with an [`ApiKey`](../struct.ApiKey.html) and the
+	/// default settings
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Self::from(super::Client::new(key))
+	}
+
+	/// the async client, e.g. to build requests for [`send`](#method.send)
+	#[must_use]
+	pub fn as_async(&self) -> &super::Client {
+		&self.client
+	}
+
+	/// send any request of the async client and wait for the response
+	///
+	/// With the `reqwest` feature the request fails with
+	/// [`Error::Connection`](../enum.Error.html#variant.Connection) if the
+	/// tokio runtime could not be created.
+	///
+	/// # Panics
+	///
+	/// if called within a future run by a `futures` executor, or with the
+	/// `reqwest` feature within a tokio runtime
+	pub fn send<R, T>(&self, request: R) -> Result<T, Error>
+	where
+		R: IntoFuture<Output = Result<T, Error>>,
+	{
+		const IN_ASYNC_CONTEXT: &str =
+			"the blocking client cannot be used within an async context, use yt_api::Client instead";
+
+		if futures::executor::enter().is_err() {
+			panic!("{}", IN_ASYNC_CONTEXT);
+		}
+		#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+		{
+			if tokio::runtime::Handle::try_current().is_ok() {
+				panic!("{}", IN_ASYNC_CONTEXT);
+			}
+			match &self.runtime {
+				Ok(runtime) => runtime.block_on(request.into_future()),
+				Err(error) => Err(Error::Connection {
+					url: self.client.base_url.clone(),
+					string: format!("failed to create the tokio runtime: {}", error),
+				}),
+			}
+		}
+		#[cfg(not(all(feature = "reqwest", not(target_arch = "wasm32"))))]
+		futures::executor::block_on(request.into_future())
+	}
+
+	/// look up videos by their ids
+	pub fn videos<I>(&self, ids: I) -> Result<videos::Response, Error>
+	where
+		I: IntoIterator,
+		I::Item: Into<VideoId>,
+	{
+		self.send(self.client.videos().ids(ids))
+	}
+
+	/// search for videos, channels and playlists matching `query`
+	pub fn search(&self, query: impl Into<String>) -> Result<search::Response, Error> {
+		self.send(self.client.search().q(query))
+	}
+
+	/// look up a channel by its id
+	pub fn channel(&self, id: impl Into<ChannelId>) -> Result<channels::Response, Error> {
+		self.send(self.client.channels().id(id))
+	}
+
+	/// first page of the items of a playlist
+	pub fn playlist_items(
+		&self,
+		playlist_id: impl Into<PlaylistId>,
+	) -> Result<playlistitems::Response, Error> {
+		self.send(self.client.playlist_items().playlist_id(playlist_id))
+	}
+}
+
+impl From<super::Client> for Client {
+	fn from(client: super::Client) -> Self {
+		Self {
+			client,
+			#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+			runtime: tokio::runtime::Builder::new_current_thread()
+				.enable_all()
+				.build()
+				.map(Arc::new)
+				.map_err(|error| error.to_string()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::Client;
+	use crate::{testing::MockTransport, ApiKey};
+
+	#[test]
+	fn sends_requests_of_the_async_client() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#)
+			.expect(
+				"statistics",
+				200,
+				r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#,
+			);
+		let client = Client::from(crate::Client::with_transport(
+			ApiKey::new("key"),
+			mock.clone(),
+		));
+
+		let response = client.videos(vec!["DnJgoWDxG2A"]).unwrap();
+		assert_eq!(response.items[0].id, "DnJgoWDxG2A");
+
+		let request = client.as_async().videos().id("DnJgoWDxG2A").statistics();
+		let response = client.send(request).unwrap();
+		assert_eq!(response.items[0].id, "DnJgoWDxG2A");
+		mock.assert_done();
+	}
+}
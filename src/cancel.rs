@@ -0,0 +1,121 @@
+//! cancellation of requests from outside of their futures
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	task::{Poll, Waker},
+};
+
+/// flag cancelling the requests it was passed to with `with_cancel`, e.g. to
+/// abort bulk jobs when a server shuts down
+///
+/// Dropping the future of a request cancels it as well, the token is for
+/// work whose futures are owned by someone else. A cancelled request fails
+/// with [`Error::Cancelled`](enum.Error.html#variant.Cancelled) before its
+/// next attempt, so a stream of pages ends before its next page and a
+/// retrying request stops waiting for its next retry.
+///
+/// ```rust,no_run
+/// # use futures::StreamExt;
+/// # use yt_api::{pagination::Paginated, ApiKey, CancellationToken, Client, Error};
+/// #
+/// # futures::executor::block_on(async {
+/// let token = CancellationToken::new();
+/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+/// let mut pages = client.playlist_items().playlist_id("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL").with_cancel(token.clone()).pages();
+///
+/// // e.g. in the shutdown handler of a server
+/// token.cancel();
+///
+/// // the stream ends instead of requesting the next page
+/// assert!(matches!(pages.next().await, Some(Err(Error::Cancelled { .. }))));
+/// assert!(pages.next().await.is_none());
+/// # });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<State>);
+
+#[derive(Debug, Default)]
+struct State {
+	cancelled: AtomicBool,
+	/// tasks waiting in [`CancellationToken::cancelled`]
+	wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// cancel the requests of this token and its clones, the token cannot be
+	/// reset
+	pub fn cancel(&self) {
+		self.0.cancelled.store(true, Ordering::SeqCst);
+		for waker in self.0.wakers.lock().unwrap().drain(..) {
+			waker.wake();
+		}
+	}
+
+	#[must_use]
+	pub fn is_cancelled(&self) -> bool {
+		self.0.cancelled.load(Ordering::SeqCst)
+	}
+
+	/// resolves once the token is cancelled
+	pub(crate) async fn cancelled(&self) {
+		futures::future::poll_fn(|cx| {
+			if self.is_cancelled() {
+				return Poll::Ready(());
+			}
+			self.0.wakers.lock().unwrap().push(cx.waker().clone());
+			// cancelled while the waker was registered
+			if self.is_cancelled() {
+				Poll::Ready(())
+			} else {
+				Poll::Pending
+			}
+		})
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use futures::StreamExt;
+
+	use super::CancellationToken;
+	use crate::{pagination::Paginated, testing::MockTransport, ApiKey, Client, Error};
+
+	#[test]
+	fn cancelled_stream_ends_before_its_next_page() {
+		let page = r#"{ "nextPageToken": "next", "items": [{ "id": "DnJgoWDxG2A" }] }"#;
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/playlistItems?", 200, page)
+			.expect("/playlistItems?", 200, page);
+		let token = CancellationToken::new();
+		let client = Client::with_transport(ApiKey::new("key"), mock.clone());
+		let mut pages = client
+			.playlist_items()
+			.playlist_id("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")
+			.with_cancel(token.clone())
+			.pages();
+
+		futures::executor::block_on(async {
+			assert!(pages.next().await.unwrap().is_ok());
+			assert!(pages.next().await.unwrap().is_ok());
+
+			token.cancel();
+			assert!(matches!(
+				pages.next().await,
+				Some(Err(Error::Cancelled { .. }))
+			));
+			assert!(pages.next().await.is_none());
+		});
+		assert_eq!(mock.requests().len(), 2);
+	}
+}
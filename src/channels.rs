@@ -0,0 +1,625 @@
+use std::collections::HashMap;
+
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::{PageInfo, Thumbnail, Thumbnails};
+pub use super::Error;
+use super::{
+	error::{InvalidParameter, MissingAccessToken, Serialization},
+	ids::ChannelRef,
+	limits,
+	pagination::{self, Paginated},
+	playlistitems::{PlaylistItems, PlaylistResult},
+	request::{self, Request},
+	videos::{VideoResult, Videos},
+	AccessToken, ApiKey, ChannelId, Client, LanguageCode, Localized, Timestamp,
+};
+
+/// request struct for the channels endpoint
+///
+/// Exactly one of the filters [`id`](#method.id),
+/// [`for_handle`](#method.for_handle), [`for_username`](#method.for_username),
+/// [`mine`](#method.mine) and [`managed_by_me`](#method.managed_by_me) has to
+/// be set.
+///
+/// ```rust
+/// # use yt_api::{ApiKey, Client, Error, Request};
+/// #
+/// # futures::executor::block_on(async {
+/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+/// let channels = || client.channels();
+///
+/// let invalid = vec![
+///     (channels().id("UC_x5XG1OV2P6uZZ5FSM9Ttw").for_handle("@GoogleDevelopers"), "id cannot be combined with forHandle"),
+///     (channels().id("UC_x5XG1OV2P6uZZ5FSM9Ttw").for_username("GoogleDevelopers"), "id cannot be combined with forUsername"),
+///     (channels().id("UC_x5XG1OV2P6uZZ5FSM9Ttw").mine(), "id cannot be combined with mine"),
+///     (channels().for_handle("@GoogleDevelopers").for_username("GoogleDevelopers"), "forHandle cannot be combined with forUsername"),
+///     (channels().for_username("GoogleDevelopers").managed_by_me(), "forUsername cannot be combined with managedByMe"),
+///     (channels().mine().managed_by_me(), "mine cannot be combined with managedByMe"),
+/// ];
+/// for (channels, reason) in invalid {
+///     match channels.build_url() {
+///         Err(Error::InvalidRequest { reason: actual }) => assert_eq!(actual, reason),
+///         result => panic!("{} was not reported: {:?}", reason, result),
+///     }
+/// }
+///
+/// let result = client.channels().id("UC_x5XG1OV2P6uZZ5FSM9Ttw").mine().await;
+/// assert!(matches!(result, Err(Error::InvalidRequest { .. })));
+///
+/// let result = client.channels().mine().await;
+/// assert!(matches!(result, Err(Error::MissingAccessToken { .. })));
+///
+/// match client.channels().build_url() {
+///     Err(Error::InvalidParameter { field, message }) => {
+///         assert_eq!(field, "filter");
+///         assert_eq!(message, "one of id, forHandle, forUsername, mine, managedByMe is required");
+///     }
+///     result => panic!("the missing filter was not reported: {:?}", result),
+/// }
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Channels {
+	data: ChannelsData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChannelsData {
+	#[serde(skip)]
+	client: Client,
+	key: ApiKey,
+	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	id: Option<ChannelId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	for_handle: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	for_username: Option<String>,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	mine: bool,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	managed_by_me: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	hl: Option<LanguageCode>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_results: Option<u8>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	on_behalf_of_content_owner: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	page_token: Option<String>,
+}
+
+impl Channels {
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "channels";
+
+	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).channels()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
+		Self {
+			data: ChannelsData {
+				key: client.key.clone(),
+				client,
+				part: String::from("snippet,contentDetails"),
+				fields: None,
+				id: None,
+				for_handle: None,
+				for_username: None,
+				mine: false,
+				managed_by_me: false,
+				hl: None,
+				max_results: None,
+				on_behalf_of_content_owner: None,
+				page_token: None,
+			},
+		}
+	}
+
+	/// the channel to look up
+	#[must_use]
+	pub fn id(mut self, id: impl Into<ChannelId>) -> Self {
+		self.data.id = Some(id.into());
+		self
+	}
+
+	/// look up the channel of a handle, with or without the leading `@`
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	///
+	/// for handle in &["@GoogleDevelopers", "GoogleDevelopers", " @GoogleDevelopers "] {
+	///     let url = client.channels().for_handle(*handle).build_url().unwrap();
+	///     assert!(url.split('&').any(|p| p == "forHandle=%40GoogleDevelopers"), "{}", url);
+	/// }
+	/// ```
+	#[must_use]
+	pub fn for_handle(mut self, for_handle: impl AsRef<str>) -> Self {
+		let handle = for_handle.as_ref().trim().trim_start_matches('@');
+		self.data.for_handle = Some(format!("@{}", handle));
+		self
+	}
+
+	/// look up the channel of a legacy youtube username
+	#[must_use]
+	pub fn for_username(mut self, for_username: impl Into<String>) -> Self {
+		self.data.for_username = Some(for_username.into());
+		self
+	}
+
+	/// list the channels of the authorized user, requires an
+	/// [`access_token`](#method.access_token)
+	#[must_use]
+	pub fn mine(mut self) -> Self {
+		self.data.mine = true;
+		self
+	}
+
+	/// list the channels managed by the authorized content owner, requires an
+	/// [`access_token`](#method.access_token) and
+	/// [`on_behalf_of_content_owner`](#method.on_behalf_of_content_owner)
+	#[must_use]
+	pub fn managed_by_me(mut self) -> Self {
+		self.data.managed_by_me = true;
+		self
+	}
+
+	/// return the [`localized`](struct.Snippet.html#structfield.localized)
+	/// title and description of the snippet in this language
+	///
+	/// ```rust
+	/// # use yt_api::{channels::Response, ApiKey, Client, Request};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let url = client.channels().id("UC_x5XG1OV2P6uZZ5FSM9Ttw").hl("de").localizations().build_url().unwrap();
+	/// assert!(url.contains("part=snippet%2CcontentDetails%2Clocalizations&"));
+	/// assert!(url.ends_with("&hl=de"));
+	///
+	/// // captured with hl=de
+	/// let response: Response = serde_json::from_str(r#"{ "items": [{
+	///     "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw",
+	///     "snippet": {
+	///         "title": "Google for Developers",
+	///         "defaultLanguage": "en",
+	///         "localized": { "title": "Google for Developers", "description": "Neuigkeiten für Entwickler" }
+	///     },
+	///     "localizations": {
+	///         "de": { "title": "Google for Developers", "description": "Neuigkeiten für Entwickler" }
+	///     }
+	/// }] }"#).unwrap();
+	///
+	/// let channel = &response.items[0];
+	/// let localized = channel.snippet.localized.as_ref().unwrap();
+	/// assert_eq!(localized.description.as_deref(), Some("Neuigkeiten für Entwickler"));
+	/// assert_eq!(channel.localizations["de"], *localized);
+	/// ```
+	#[must_use]
+	pub fn hl(mut self, hl: impl Into<LanguageCode>) -> Self {
+		self.data.hl = Some(hl.into());
+		self
+	}
+
+	/// request the `localizations` part with the translations of the title
+	/// and description into every language
+	#[must_use]
+	pub fn localizations(mut self) -> Self {
+		self.data.part.push_str(",localizations");
+		self
+	}
+
+	/// request the `statistics` part with the view, subscriber and video
+	/// counts
+	#[must_use]
+	pub fn statistics(mut self) -> Self {
+		self.data.part.push_str(",statistics");
+		self
+	}
+
+	#[must_use]
+	pub fn max_results(mut self, max_results: impl Into<u8>) -> Self {
+		self.data.max_results = Some(max_results.into());
+		self
+	}
+
+	/// act on behalf of the content owner with the given id, only usable by
+	/// youtube content partners authorized with an
+	/// [`access_token`](#method.access_token) of a cms account linked to it
+	#[must_use]
+	pub fn on_behalf_of_content_owner(
+		mut self,
+		on_behalf_of_content_owner: impl Into<String>,
+	) -> Self {
+		self.data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
+		self
+	}
+
+	#[must_use]
+	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
+		self.data.page_token = Some(page_token.into());
+		self
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+}
+
+/// stream of all videos uploaded by a channel, see
+/// [`Client::channel_uploads`](../struct.Client.html#method.channel_uploads)
+///
+/// ```no_run
+/// # use futures::{pin_mut, StreamExt};
+/// # use yt_api::{channels::channel_uploads, ApiKey};
+/// #
+/// # futures::executor::block_on(async {
+/// let key = ApiKey::new("your-youtube-api-key");
+/// let videos = channel_uploads(key, "UC_x5XG1OV2P6uZZ5FSM9Ttw").await.unwrap();
+/// pin_mut!(videos);
+///
+/// while let Some(video) = videos.next().await {
+///     println!("{:?}", video.unwrap().snippet.title);
+/// }
+/// # });
+/// ```
+#[cfg(any(feature = "surf", feature = "reqwest"))]
+pub async fn channel_uploads(
+	key: ApiKey,
+	channel_id: impl Into<ChannelId>,
+) -> Result<impl Stream<Item = Result<VideoResult, Error>>, Error> {
+	Ok(Client::new(key).channel_uploads(channel_id).await?.videos())
+}
+
+/// uploads playlist of a channel, whose entries can be streamed from the
+/// newest to the oldest
+///
+/// Deleted videos are skipped, as are private videos unless the client has
+/// an access token. The streams end after the last page or after the first
+/// error.
+#[derive(Debug)]
+pub struct ChannelUploads {
+	client: Client,
+	playlist_items: Option<PlaylistItems>,
+}
+
+impl ChannelUploads {
+	/// look up the uploads playlist of the channel, a channel without one has
+	/// no uploads and an unknown channel fails with
+	/// [`Error::InvalidParameter`](../enum.Error.html#variant.InvalidParameter)
+	pub(crate) async fn find(client: Client, channel_id: ChannelId) -> Result<Self, Error> {
+		let response = client
+			.channels()
+			.id(channel_id.clone())
+			.fields("items(contentDetails/relatedPlaylists/uploads)")
+			.await?;
+		let channel = response
+			.items
+			.into_iter()
+			.next()
+			.ok_or_else(|| Error::InvalidParameter {
+				field: String::from("channelId"),
+				message: format!("there is no channel {}", channel_id),
+			})?;
+		let playlist_items = channel
+			.content_details
+			.and_then(|content_details| content_details.related_playlists.uploads)
+			.map(|uploads| {
+				client
+					.playlist_items()
+					.playlist_id(uploads)
+					.status()
+					.max_results(Videos::MAX_IDS as u8)
+			});
+		Ok(Self {
+			client,
+			playlist_items,
+		})
+	}
+
+	/// stream of the entries of the uploads playlist, costing one quota unit
+	/// per 50 entries
+	pub fn items(self) -> impl Stream<Item = Result<PlaylistResult, Error>> {
+		self.pages()
+			.map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+			.try_flatten()
+	}
+
+	/// stream of the uploaded videos with all the parts of a
+	/// [`Videos`](../videos/struct.Videos.html) request, looked up in
+	/// batches of 50 and costing two quota units per batch
+	pub fn videos(self) -> impl Stream<Item = Result<VideoResult, Error>> {
+		let client = self.client.clone();
+		self.pages()
+			.and_then(move |page| {
+				let ids = page
+					.into_iter()
+					.map(|item| item.snippet.resource_id.video_id)
+					.collect::<Vec<_>>();
+				let videos = Some(ids)
+					.filter(|ids| !ids.is_empty())
+					.map(|ids| client.videos().ids(ids));
+				async move {
+					match videos {
+						Some(videos) => Ok(videos.await?.items),
+						None => Ok(Vec::new()),
+					}
+				}
+			})
+			.map_ok(|videos| stream::iter(videos.into_iter().map(Ok)))
+			.try_flatten()
+	}
+
+	/// stream of the available entries of each page
+	fn pages(self) -> impl Stream<Item = Result<Vec<PlaylistResult>, Error>> {
+		let include_private = self.client.access_token.is_some();
+		stream::iter(self.playlist_items.map(Paginated::pages))
+			.flatten()
+			.map_ok(move |page| {
+				page.items
+					.into_iter()
+					.filter(|item| {
+						match item
+							.status
+							.as_ref()
+							.map(|status| status.privacy_status.as_str())
+						{
+							Some("privacyStatusUnspecified") => false,
+							Some("private") => include_private,
+							_ => true,
+						}
+					})
+					.collect()
+			})
+	}
+}
+
+/// id of the channel a [`ChannelRef`] refers to, see
+/// [`Client::resolve_channel`](../struct.Client.html#method.resolve_channel)
+pub(crate) async fn resolve(client: &Client, input: &str) -> Result<Option<ChannelId>, Error> {
+	let lookup = |channels: Channels| async move {
+		let response = channels.fields("items(id)").await?;
+		Ok::<_, Error>(response.items.into_iter().next().map(|channel| channel.id))
+	};
+	match ChannelRef::parse(input)? {
+		ChannelRef::Id(id) => Ok(Some(id)),
+		ChannelRef::Handle(handle) => lookup(client.channels().for_handle(handle)).await,
+		ChannelRef::Username(username) => lookup(client.channels().for_username(username)).await,
+		ChannelRef::Name(name) => {
+			let by_handle = lookup(client.channels().for_handle(name)).await?;
+			let by_username = lookup(client.channels().for_username(name)).await?;
+			match (by_handle, by_username) {
+				(Some(by_handle), Some(by_username)) if by_handle != by_username => {
+					InvalidParameter {
+						field: "forHandle",
+						message: format!(
+							"{:?} is the handle of {} but the username of {}",
+							name, by_handle, by_username
+						),
+					}
+					.fail()
+				}
+				(by_handle, by_username) => Ok(by_handle.or(by_username)),
+			}
+		}
+	}
+}
+
+impl ChannelsData {
+	const OWNER_FILTERS: &'static [&'static str] = &["mine", "managedByMe"];
+
+	fn check_filters(&self) -> Result<(), Error> {
+		let filters = [
+			("id", self.id.is_some()),
+			("forHandle", self.for_handle.is_some()),
+			("forUsername", self.for_username.is_some()),
+			("mine", self.mine),
+			("managedByMe", self.managed_by_me),
+		];
+		match request::exactly_one_filter(&filters)? {
+			filter
+				if Self::OWNER_FILTERS.contains(&filter) && self.client.access_token.is_none() =>
+			{
+				MissingAccessToken {
+					required_by: format!("the {} filter", filter),
+				}
+				.fail()
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+impl Request for Channels {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.check_filters()?;
+		if let Some(hl) = &data.hl {
+			hl.validate("hl")?;
+		}
+		limits::check(
+			"maxResults",
+			&limits::MAX_RESULTS,
+			data.max_results.map(u16::from),
+		)?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
+}
+
+request::request_methods!(Channels => Response, "youtube#channelListResponse");
+
+impl Paginated for Channels {
+	type Response = Response;
+
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Response {
+	pub kind: String,
+	pub etag: String,
+	pub next_page_token: Option<String>,
+	pub prev_page_token: Option<String>,
+	pub page_info: PageInfo,
+	pub items: Vec<Channel>,
+}
+
+pagination::page_methods!(Response => Channel);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Channel {
+	pub kind: String,
+	pub etag: String,
+	pub id: ChannelId,
+	pub snippet: Snippet,
+	pub content_details: Option<ContentDetails>,
+	pub statistics: Option<Statistics>,
+	/// translations of the title and description by language code
+	pub localizations: HashMap<String, Localized>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl Channel {
+	/// url of the page of the channel by its id, see
+	/// [`ChannelId::url`](../struct.ChannelId.html#method.url)
+	#[must_use]
+	pub fn channel_url(&self) -> String {
+		self.id.url()
+	}
+
+	/// url of the page of the channel by its `snippet.customUrl`, which is
+	/// its `@handle` or, for older channels, a custom name
+	///
+	/// ```rust
+	/// # use yt_api::channels::Channel;
+	/// let channel = |json| serde_json::from_str::<Channel>(json).unwrap();
+	///
+	/// let handle = channel(r#"{ "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw", "snippet": { "customUrl": "@googledevelopers" } }"#);
+	/// assert_eq!(handle.channel_url(), "https://www.youtube.com/channel/UC_x5XG1OV2P6uZZ5FSM9Ttw");
+	/// assert_eq!(handle.custom_url().as_deref(), Some("https://www.youtube.com/@googledevelopers"));
+	///
+	/// let name = channel(r#"{ "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw", "snippet": { "customUrl": "googledevelopers" } }"#);
+	/// assert_eq!(name.custom_url().as_deref(), Some("https://www.youtube.com/c/googledevelopers"));
+	///
+	/// let without = channel(r#"{ "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw", "snippet": {} }"#);
+	/// assert_eq!(without.custom_url(), None);
+	/// assert_eq!(without.channel_url(), "https://www.youtube.com/channel/UC_x5XG1OV2P6uZZ5FSM9Ttw");
+	/// ```
+	#[must_use]
+	pub fn custom_url(&self) -> Option<String> {
+		let custom_url = self.snippet.custom_url.as_deref()?.trim();
+		match custom_url {
+			"" => None,
+			handle if handle.starts_with('@') => {
+				Some(format!("https://www.youtube.com/{}", handle))
+			}
+			name => Some(format!("https://www.youtube.com/c/{}", name)),
+		}
+	}
+
+	/// the largest thumbnail of the channel, see
+	/// [`Thumbnails::best`](struct.Thumbnails.html#method.best)
+	#[must_use]
+	pub fn thumbnail(&self) -> Option<&Thumbnail> {
+		self.snippet.thumbnails.as_ref()?.best()
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snippet {
+	pub title: Option<String>,
+	pub description: Option<String>,
+	pub custom_url: Option<String>,
+	pub published_at: Option<Timestamp>,
+	pub thumbnails: Option<Thumbnails>,
+	pub default_language: Option<String>,
+	/// title and description in the language requested with
+	/// [`hl`](struct.Channels.html#method.hl)
+	pub localized: Option<Localized>,
+	pub country: Option<String>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// counts of the `statistics` part, the api sends them as strings
+///
+/// Channels can hide their subscriber count, which is then left out by the
+/// api and stays `None` instead of being reported as zero.
+///
+/// ```rust
+/// # use yt_api::channels::Statistics;
+/// let public: Statistics = serde_json::from_str(r#"{
+///     "viewCount": "252437618", "subscriberCount": "2420000", "hiddenSubscriberCount": false, "videoCount": "6064"
+/// }"#).unwrap();
+/// assert_eq!(public.subscriber_count.as_deref(), Some("2420000"));
+/// assert_eq!(public.hidden_subscriber_count, Some(false));
+///
+/// let hidden: Statistics = serde_json::from_str(r#"{
+///     "viewCount": "1302", "hiddenSubscriberCount": true, "videoCount": "12"
+/// }"#).unwrap();
+/// assert_eq!(hidden.subscriber_count, None);
+/// assert_eq!(hidden.hidden_subscriber_count, Some(true));
+///
+/// let unknown: Statistics = serde_json::from_str(r#"{ "videoCount": "12" }"#).unwrap();
+/// assert_eq!(unknown.subscriber_count, None);
+/// assert_eq!(unknown.hidden_subscriber_count, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Statistics {
+	pub view_count: Option<String>,
+	pub subscriber_count: Option<String>,
+	pub hidden_subscriber_count: Option<bool>,
+	pub video_count: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ContentDetails {
+	pub related_playlists: RelatedPlaylists,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RelatedPlaylists {
+	pub likes: Option<String>,
+	/// id of the playlist with all uploaded videos of the channel
+	pub uploads: Option<String>,
+}
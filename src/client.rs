@@ -0,0 +1,782 @@
+use std::{
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use futures::Stream;
+
+#[cfg(any(
+	feature = "curl-client",
+	all(feature = "reqwest", not(target_arch = "wasm32"))
+))]
+use super::Proxy;
+use super::{
+	channels::{self, ChannelUploads, Channels},
+	commentthreads::CommentThreads,
+	guidecategories::GuideCategories,
+	members::Members,
+	membershipslevels::MembershipsLevels,
+	observer::{LogObserver, RequestObserver},
+	playlistitems::PlaylistItems,
+	playlists::Playlists,
+	ratelimit::RateLimiter,
+	search::SearchList,
+	transport::Transport,
+	videocategories::VideoCategories,
+	videos::{Batch, Videos},
+	AccessToken, ApiKey, CancellationToken, ChannelId, Error, RetryPolicy, VideoId, BASE_URL,
+};
+
+/// reusable client holding the [`ApiKey`](struct.ApiKey.html) and the settings
+/// shared by all requests created from it
+///
+/// ```rust,no_run
+/// # use yt_api::{ApiKey, Client, RetryPolicy};
+/// #
+/// # futures::executor::block_on(async {
+/// let client = Client::new(ApiKey::new("your-youtube-api-key")).retry(RetryPolicy::exponential(3));
+/// let result = client.search().q("rust lang").await;
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+	pub(crate) key: ApiKey,
+	pub(crate) access_token: Option<AccessToken>,
+	pub(crate) base_url: String,
+	pub(crate) retry: Option<RetryPolicy>,
+	pub(crate) timeout: Option<Duration>,
+	pub(crate) cancellation: Option<CancellationToken>,
+	pub(crate) observer: Arc<dyn RequestObserver>,
+	pub(crate) quota_used: Arc<AtomicU64>,
+	pub(crate) quota_budget: Option<u64>,
+	pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+	pub(crate) user_agent: String,
+	pub(crate) headers: Vec<(String, String)>,
+	pub(crate) transport: Arc<dyn Transport>,
+}
+
+impl Client {
+	/// `User-Agent` header sent unless another one is set with
+	/// [`user_agent`](#method.user_agent)
+	pub const DEFAULT_USER_AGENT: &'static str = concat!("yt-api/", env!("CARGO_PKG_VERSION"));
+
+	/// create client with an [`ApiKey`](struct.ApiKey.html), sending requests
+	/// with the default [`Transport`](transport/trait.Transport.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Self::with_transport(key, super::transport::default_transport())
+	}
+
+	/// create client with an [`ApiKey`](struct.ApiKey.html), sending requests
+	/// with a custom [`Transport`](transport/trait.Transport.html)
+	#[must_use]
+	pub fn with_transport(key: ApiKey, transport: Arc<dyn Transport>) -> Self {
+		Self {
+			key,
+			access_token: None,
+			base_url: String::from(BASE_URL),
+			retry: None,
+			timeout: None,
+			cancellation: None,
+			observer: Arc::new(LogObserver),
+			quota_used: Arc::new(AtomicU64::new(0)),
+			quota_budget: None,
+			rate_limiter: None,
+			user_agent: String::from(Self::DEFAULT_USER_AGENT),
+			headers: Vec::new(),
+			transport,
+		}
+	}
+
+	/// authorize all requests with an [`AccessToken`](struct.AccessToken.html)
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.access_token = Some(access_token);
+		self
+	}
+
+	/// send all requests to another api root than the default
+	/// `https://www.googleapis.com/youtube/v3`, e.g. a proxy or a mock server
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key")).base_url("http://localhost:8080/youtube/v3");
+	/// ```
+	#[must_use]
+	pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+		self.base_url = base_url.into();
+		self
+	}
+
+	/// retry transient failures of all requests according to a
+	/// [`RetryPolicy`](struct.RetryPolicy.html)
+	#[must_use]
+	pub fn retry(mut self, retry: RetryPolicy) -> Self {
+		self.retry = Some(retry);
+		self
+	}
+
+	/// abort every attempt of a request which takes longer than `timeout`
+	/// with [`Error::Timeout`](enum.Error.html#variant.Timeout)
+	#[must_use]
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// fail all requests with [`Error::Cancelled`](enum.Error.html#variant.Cancelled)
+	/// once `token` is cancelled, see [`CancellationToken`](struct.CancellationToken.html)
+	///
+	/// A request waiting to be retried stops waiting once the token is
+	/// cancelled:
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, CancellationToken, Client, RetryPolicy};
+	/// let token = CancellationToken::new();
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"))
+	///     .retry(RetryPolicy::exponential(5))
+	///     .with_cancel(token.clone());
+	///
+	/// // e.g. in the shutdown handler of a server
+	/// token.cancel();
+	/// ```
+	#[must_use]
+	pub fn with_cancel(mut self, token: CancellationToken) -> Self {
+		self.cancellation = Some(token);
+		self
+	}
+
+	/// report the requests of this client to `observer` instead of logging
+	/// them with [`LogObserver`](observer/struct.LogObserver.html)
+	#[must_use]
+	pub fn observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+		self.observer = observer;
+		self
+	}
+
+	/// fail requests with
+	/// [`Error::QuotaBudgetExceeded`](enum.Error.html#variant.QuotaBudgetExceeded)
+	/// before sending them if they would raise the
+	/// [`quota_used`](#method.quota_used) above `budget`
+	#[must_use]
+	pub fn quota_budget(mut self, budget: u64) -> Self {
+		self.quota_budget = Some(budget);
+		self
+	}
+
+	/// quota units spent by the requests of this client and its clones, every
+	/// attempt of a request is charged with the cost of its endpoint
+	#[must_use]
+	pub fn quota_used(&self) -> u64 {
+		self.quota_used.load(Ordering::SeqCst)
+	}
+
+	/// send at most `requests_per_second` requests per second on average,
+	/// allowing short bursts of up to `burst` requests
+	///
+	/// Requests waiting for the limiter are sent in the order they were
+	/// started. The limit is shared by the clones of this client.
+	///
+	/// # Panics
+	///
+	/// if `requests_per_second` is not positive
+	#[must_use]
+	pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+		assert!(
+			requests_per_second > 0.0,
+			"requests_per_second must be positive"
+		);
+		self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_second, burst)));
+		self
+	}
+
+	/// identify the requests of this client with another `User-Agent` header
+	/// than [`DEFAULT_USER_AGENT`](#associatedconstant.DEFAULT_USER_AGENT)
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key")).user_agent("video-archiver/1.2");
+	/// ```
+	///
+	/// A user agent which is not a valid header value, e.g. because it
+	/// contains a line break, fails the requests with
+	/// [`Error::InvalidParameter`](enum.Error.html#variant.InvalidParameter)
+	/// before they are sent.
+	#[must_use]
+	pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+		self.user_agent = user_agent.into();
+		self
+	}
+
+	/// send the header `name` with every request of this client, e.g. for the
+	/// authentication of a proxy
+	///
+	/// Setting a header again replaces its value, names are compared case
+	/// insensitively. The `User-Agent` is set with
+	/// [`user_agent`](#method.user_agent) instead.
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key")).header("X-Proxy-Auth", "secret");
+	/// ```
+	///
+	/// Requests with an invalid header name or value fail with
+	/// [`Error::InvalidParameter`](enum.Error.html#variant.InvalidParameter)
+	/// before they are sent:
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Error};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("key")).header("X-Proxy-Auth", "secret\r\nHost: evil");
+	/// let result = client.videos().id("DnJgoWDxG2A").await;
+	/// assert!(matches!(result, Err(Error::InvalidParameter { field, .. }) if field == "X-Proxy-Auth"));
+	/// # });
+	/// ```
+	#[must_use]
+	pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		let (name, value) = (name.into(), value.into());
+		self.headers
+			.retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+		self.headers.push((name, value));
+		self
+	}
+
+	/// send all requests through `proxy`, replacing the transport of the client
+	/// with a new one of the enabled http backend
+	///
+	/// Surf only supports proxies with its curl client, which is enabled by
+	/// default. Without an explicit proxy [`Client::new`](#method.new) uses
+	/// [`Proxy::from_env`](struct.Proxy.html#method.from_env).
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Proxy};
+	/// let proxy = Proxy::new("http://proxy.example.com:3128")
+	///     .unwrap()
+	///     .basic_auth("user", "secret")
+	///     .no_proxy(vec!["localhost"]);
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key")).proxy(proxy).unwrap();
+	/// ```
+	///
+	/// Fails with [`Error::InvalidParameter`](enum.Error.html#variant.InvalidParameter)
+	/// if the http client cannot be created for the proxy.
+	#[cfg(any(
+		feature = "curl-client",
+		all(feature = "reqwest", not(target_arch = "wasm32"))
+	))]
+	pub fn proxy(mut self, proxy: Proxy) -> Result<Self, Error> {
+		self.transport =
+			super::transport::proxy_transport(&proxy).map_err(|error| Error::InvalidParameter {
+				field: String::from("proxy"),
+				message: format!("failed to create the http client: {}", error),
+			})?;
+		Ok(self)
+	}
+
+	/// id of the channel a user refers to by a channel id, an `@handle`, a
+	/// legacy username or a channel url
+	///
+	/// Urls of the forms `youtube.com/channel/UC…`, `youtube.com/@handle`,
+	/// `youtube.com/user/name` and `youtube.com/c/name` are recognized. Ids
+	/// are returned without a request, everything else is looked up with the
+	/// channels endpoint and resolves to `None` if there is no such channel.
+	/// A bare name is looked up both as handle and as username, and fails with
+	/// [`Error::InvalidParameter`](enum.Error.html#variant.InvalidParameter) if
+	/// it refers to two different channels.
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let id = client.resolve_channel("https://www.youtube.com/@GoogleDevelopers").await.unwrap();
+	/// # });
+	/// ```
+	pub async fn resolve_channel(&self, input: &str) -> Result<Option<ChannelId>, Error> {
+		channels::resolve(self, input).await
+	}
+
+	/// whether the api accepts the key of this client, checked with a request
+	/// for the video categories of a region costing one quota unit
+	///
+	/// Resolves to `false` if the key is invalid, expired, restricted to
+	/// other origins or its project has not enabled the api. Other failures,
+	/// like an exceeded quota or a `forbidden` request, are returned as
+	/// errors, since they say nothing about the key.
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::from_env().unwrap());
+	/// if !client.verify_key().await.unwrap() {
+	///     eprintln!("the api key was rejected");
+	/// }
+	/// # });
+	/// ```
+	pub async fn verify_key(&self) -> Result<bool, Error> {
+		const REJECTED_KEY_REASONS: &[&str] = &[
+			"keyInvalid",
+			"keyExpired",
+			"accessNotConfigured",
+			"ipRefererBlocked",
+		];
+		let request = self.video_categories().region_code("US").fields("etag");
+		match request.await {
+			Ok(_) => Ok(true),
+			Err(Error::Api {
+				status,
+				reason,
+				message,
+				..
+			}) if REJECTED_KEY_REASONS.contains(&reason.as_deref().unwrap_or_default())
+				|| (status == 400 && message.starts_with("API key not valid")) =>
+			{
+				Ok(false)
+			}
+			Err(error) => Err(error),
+		}
+	}
+
+	/// look up any number of videos with the default parts, see
+	/// [`Videos::batches`](videos/struct.Videos.html#method.batches) to
+	/// request other parts
+	pub fn videos_batch<I>(&self, ids: I, concurrency: usize) -> impl Stream<Item = Batch>
+	where
+		I: IntoIterator,
+		I::Item: Into<VideoId>,
+	{
+		self.videos().batches(ids, concurrency)
+	}
+
+	/// look up the uploads playlist of a channel, to stream the videos the
+	/// channel uploaded
+	///
+	/// ```rust,no_run
+	/// # use futures::TryStreamExt;
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let uploads = client.channel_uploads("UC_x5XG1OV2P6uZZ5FSM9Ttw").await.unwrap();
+	///
+	/// let mut videos = Box::pin(uploads.videos());
+	/// while let Some(video) = videos.try_next().await.unwrap() {
+	///     println!("{:?}", video.snippet.title);
+	/// }
+	/// # });
+	/// ```
+	pub async fn channel_uploads(
+		&self,
+		channel_id: impl Into<ChannelId>,
+	) -> Result<ChannelUploads, Error> {
+		ChannelUploads::find(self.clone(), channel_id.into()).await
+	}
+
+	/// create a request for the channels endpoint
+	#[must_use]
+	pub fn channels(&self) -> Channels {
+		Channels::with_client(self.clone())
+	}
+
+	/// create a request for the commentThreads endpoint
+	#[must_use]
+	pub fn comment_threads(&self) -> CommentThreads {
+		CommentThreads::with_client(self.clone())
+	}
+
+	/// create a request for the guideCategories endpoint
+	#[must_use]
+	pub fn guide_categories(&self) -> GuideCategories {
+		GuideCategories::with_client(self.clone())
+	}
+
+	/// create a request for the members endpoint, which needs an
+	/// [`access_token`](#method.access_token) of the channel owner
+	#[must_use]
+	pub fn members(&self) -> Members {
+		Members::with_client(self.clone())
+	}
+
+	/// create a request for the membershipsLevels endpoint, which needs an
+	/// [`access_token`](#method.access_token) of the channel owner
+	#[must_use]
+	pub fn memberships_levels(&self) -> MembershipsLevels {
+		MembershipsLevels::with_client(self.clone())
+	}
+
+	/// create a request for the playlistItems endpoint
+	#[must_use]
+	pub fn playlist_items(&self) -> PlaylistItems {
+		PlaylistItems::with_client(self.clone())
+	}
+
+	/// create a request for the playlists endpoint
+	#[must_use]
+	pub fn playlists(&self) -> Playlists {
+		Playlists::with_client(self.clone())
+	}
+
+	/// create a request for the search endpoint
+	#[must_use]
+	pub fn search(&self) -> SearchList {
+		SearchList::with_client(self.clone())
+	}
+
+	/// create a request for the videoCategories endpoint
+	#[must_use]
+	pub fn video_categories(&self) -> VideoCategories {
+		VideoCategories::with_client(self.clone())
+	}
+
+	/// create a request for the videos endpoint
+	#[must_use]
+	pub fn videos(&self) -> Videos {
+		Videos::with_client(self.clone())
+	}
+
+	pub(crate) fn charge_quota(&self, cost: u32) -> Result<(), Error> {
+		let budget = self.quota_budget;
+		self.quota_used
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+				let used = used + u64::from(cost);
+				match budget {
+					Some(budget) if used > budget => None,
+					_ => Some(used),
+				}
+			})
+			.map(drop)
+			.map_err(|used| Error::QuotaBudgetExceeded {
+				used,
+				cost,
+				budget: budget.unwrap_or_default(),
+			})
+	}
+
+	pub(crate) fn url(&self, path: &str, query: &str) -> String {
+		format!("{}/{}?{}", self.base_url.trim_end_matches('/'), path, query)
+	}
+
+	/// fails with [`Error::InvalidParameter`] if the user agent or one of the
+	/// headers set on this client is not a valid header
+	pub(crate) fn check_headers(&self) -> Result<(), Error> {
+		check_header("User-Agent", &self.user_agent)?;
+		self.headers
+			.iter()
+			.try_for_each(|(name, value)| check_header(name, value))
+	}
+}
+
+/// fails unless `name` is a token and `value` only contains visible ascii
+/// characters, spaces and tabs, as required by rfc 7230
+fn check_header(name: &str, value: &str) -> Result<(), Error> {
+	let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+	if name.is_empty() || !name.chars().all(is_token) {
+		return Err(Error::InvalidParameter {
+			field: String::from("header"),
+			message: format!("{:?} is not a valid header name", name),
+		});
+	}
+	if !value
+		.chars()
+		.all(|c| c == ' ' || c == '\t' || c.is_ascii_graphic())
+	{
+		return Err(Error::InvalidParameter {
+			field: name.to_string(),
+			message: String::from("is not a valid header value"),
+		});
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::Arc, time::Duration};
+
+	use futures::TryStreamExt;
+
+	use super::Client;
+	use crate::{
+		testing::MockTransport, transport::HttpRequest, ApiKey, CancellationToken, Error,
+		RetryPolicy,
+	};
+
+	fn client(mock: &Arc<MockTransport>) -> Client {
+		Client::with_transport(ApiKey::new("key"), mock.clone())
+	}
+
+	#[test]
+	fn base_url_replaces_the_api_root() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"http://localhost:8080/youtube/v3/videos?key=key&",
+			200,
+			"{}",
+		);
+		let client = client(&mock).base_url("http://localhost:8080/youtube/v3");
+
+		futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send()).unwrap();
+		mock.assert_done();
+	}
+
+	#[test]
+	fn cancel_stops_waiting_for_a_retry() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 503, "");
+		let token = CancellationToken::new();
+		let client = client(&mock)
+			.retry(RetryPolicy::exponential(5).base_delay(Duration::from_secs(3600)))
+			.with_cancel(token.clone());
+
+		futures::executor::block_on(async {
+			let request = client.videos().id("DnJgoWDxG2A").send();
+			futures::pin_mut!(request);
+			assert!(futures::poll!(&mut request).is_pending());
+
+			token.cancel();
+			assert!(matches!(request.await, Err(Error::Cancelled { .. })));
+		});
+		assert_eq!(mock.requests().len(), 1);
+	}
+
+	#[test]
+	fn user_agent_replaces_the_default() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, "{}")
+			.expect("/videos?", 200, "{}");
+		let client = client(&mock);
+
+		futures::executor::block_on(async {
+			client.videos().id("DnJgoWDxG2A").await.unwrap();
+			let client = client.user_agent("video-archiver/1.2");
+			client.videos().id("DnJgoWDxG2A").await.unwrap();
+		});
+
+		let user_agent = |request: &HttpRequest| {
+			let header = request
+				.headers
+				.iter()
+				.find(|(name, _)| name == "User-Agent");
+			header.map(|(_, value)| value.clone()).unwrap()
+		};
+		let requests = mock.requests();
+		assert_eq!(user_agent(&requests[0]), Client::DEFAULT_USER_AGENT);
+		assert!(user_agent(&requests[0]).starts_with("yt-api/"));
+		assert_eq!(user_agent(&requests[1]), "video-archiver/1.2");
+	}
+
+	#[test]
+	fn headers_are_replaced_case_insensitively() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, "{}")
+			.expect("/search?", 200, "{}");
+		let client = client(&mock)
+			.user_agent("video-archiver/1.2")
+			.header("X-Proxy-Auth", "outdated")
+			.header("x-proxy-auth", "secret")
+			.header("X-Team", "archive");
+
+		futures::executor::block_on(async {
+			client.videos().id("DnJgoWDxG2A").await.unwrap();
+			client.search().q("rust").await.unwrap();
+		});
+
+		for request in mock.requests() {
+			assert_eq!(
+				request.headers,
+				[
+					(
+						String::from("User-Agent"),
+						String::from("video-archiver/1.2")
+					),
+					(String::from("x-proxy-auth"), String::from("secret")),
+					(String::from("X-Team"), String::from("archive")),
+				],
+			);
+		}
+	}
+
+	#[test]
+	fn invalid_headers_fail_before_sending() {
+		let mock = Arc::new(MockTransport::new());
+		let client = client(&mock).user_agent("video-archiver\n");
+
+		let result = futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send());
+		assert!(
+			matches!(result, Err(Error::InvalidParameter { field, .. }) if field == "User-Agent")
+		);
+		assert!(mock.requests().is_empty());
+	}
+
+	#[test]
+	fn resolve_channel_looks_up_handles_and_usernames() {
+		let found = r#"{ "items": [{ "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw" }] }"#;
+		let mock = Arc::new(MockTransport::new());
+		for _ in 0..4 {
+			mock.expect("forHandle=%40GoogleDevelopers", 200, found);
+		}
+		for _ in 0..3 {
+			mock.expect("forUsername=GoogleDevelopers", 200, found);
+		}
+		mock.expect("forHandle=%40nobody", 200, r#"{ "items": [] }"#)
+			.expect("forHandle=%40ambiguous", 200, found)
+			.expect(
+				"forUsername=ambiguous",
+				200,
+				r#"{ "items": [{ "id": "UCVHFbqXqoYvEWM1Ddxl0QDg" }] }"#,
+			);
+		let client = client(&mock);
+
+		futures::executor::block_on(async {
+			for input in [
+				"UC_x5XG1OV2P6uZZ5FSM9Ttw",
+				"https://www.youtube.com/channel/UC_x5XG1OV2P6uZZ5FSM9Ttw",
+				"@GoogleDevelopers",
+				"https://www.youtube.com/@GoogleDevelopers/videos",
+				"https://youtube.com/user/GoogleDevelopers",
+				"https://m.youtube.com/c/GoogleDevelopers",
+				"GoogleDevelopers",
+			]
+			.iter()
+			{
+				let id = client.resolve_channel(input).await.unwrap();
+				assert_eq!(id.unwrap(), "UC_x5XG1OV2P6uZZ5FSM9Ttw", "{}", input);
+			}
+
+			assert_eq!(client.resolve_channel("@nobody").await.unwrap(), None);
+			let result = client.resolve_channel("ambiguous").await;
+			assert!(matches!(result, Err(Error::InvalidParameter { .. })));
+			let result = client
+				.resolve_channel("https://example.com/@GoogleDevelopers")
+				.await;
+			assert!(matches!(result, Err(Error::InvalidParameter { .. })));
+		});
+		mock.assert_done();
+	}
+
+	#[test]
+	fn verify_key_only_rejects_errors_about_the_key() {
+		let verify = |status, body: &str| {
+			let mock = Arc::new(MockTransport::new());
+			mock.expect("/videoCategories?", status, body);
+			futures::executor::block_on(client(&mock).verify_key())
+		};
+
+		assert!(verify(200, r#"{ "etag": "etag" }"#).unwrap());
+		let invalid = r#"{ "error": { "code": 400, "message": "API key not valid. Please pass a valid API key.", "errors": [{ "reason": "badRequest" }] } }"#;
+		assert!(!verify(400, invalid).unwrap());
+		let blocked = r#"{ "error": { "code": 403, "message": "Requests from this referer are blocked.", "errors": [{ "reason": "ipRefererBlocked" }] } }"#;
+		assert!(!verify(403, blocked).unwrap());
+		// a bad request unrelated to the key
+		let broken = r#"{ "error": { "code": 400, "message": "Invalid value for regionCode.", "errors": [{ "reason": "badRequest" }] } }"#;
+		assert!(verify(400, broken).is_err());
+	}
+
+	#[test]
+	fn channel_uploads_skips_deleted_videos() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"/channels?",
+			200,
+			r#"{ "items": [{ "contentDetails": { "relatedPlaylists": { "uploads": "UU_x5XG1OV2P6uZZ5FSM9Ttw" } } }] }"#,
+		)
+		.expect(
+			"/playlistItems?",
+			200,
+			r#"{ "nextPageToken": "next", "items": [
+				{ "snippet": { "resourceId": { "videoId": "DnJgoWDxG2A" } }, "status": { "privacyStatus": "public" } },
+				{ "snippet": { "resourceId": { "videoId": "xxxxxxxxxxx" } }, "status": { "privacyStatus": "privacyStatusUnspecified" } }
+			] }"#,
+		)
+		.expect(
+			"&id=DnJgoWDxG2A",
+			200,
+			r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#,
+		)
+		.expect(
+			"pageToken=next",
+			200,
+			r#"{ "items": [
+				{ "snippet": { "resourceId": { "videoId": "dQw4w9WgXcQ" } }, "status": { "privacyStatus": "unlisted" } }
+			] }"#,
+		)
+		.expect(
+			"&id=dQw4w9WgXcQ",
+			200,
+			r#"{ "items": [{ "id": "dQw4w9WgXcQ" }] }"#,
+		);
+		let client = client(&mock);
+
+		let videos = futures::executor::block_on(async {
+			let uploads = client
+				.channel_uploads("UC_x5XG1OV2P6uZZ5FSM9Ttw")
+				.await
+				.unwrap();
+			uploads.videos().try_collect::<Vec<_>>().await.unwrap()
+		});
+
+		let ids = videos
+			.iter()
+			.map(|video| video.id.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(ids, ["DnJgoWDxG2A", "dQw4w9WgXcQ"]);
+		mock.assert_done();
+	}
+
+	/// answers a single request with a canned videos.list body, returning the
+	/// lowercased request
+	#[cfg(feature = "curl-client")]
+	fn serve(listener: std::net::TcpListener) -> std::thread::JoinHandle<String> {
+		use std::io::{Read, Write};
+
+		std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			let mut request = [0; 4096];
+			let length = stream.read(&mut request).unwrap();
+			let body = r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#;
+			write!(
+				stream,
+				"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+				body.len(),
+				body,
+			)
+			.unwrap();
+			String::from_utf8_lossy(&request[..length]).to_lowercase()
+		})
+	}
+
+	#[cfg(feature = "curl-client")]
+	#[test]
+	fn proxy_is_used_for_all_hosts_but_the_no_proxy_list() {
+		use std::net::TcpListener;
+
+		use crate::Proxy;
+
+		let proxy_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let proxy_address = proxy_listener.local_addr().unwrap();
+		let proxy_server = serve(proxy_listener);
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		let server = serve(listener);
+
+		let proxy = Proxy::new(format!("http://{}", proxy_address))
+			.unwrap()
+			.basic_auth("user", "secret")
+			.no_proxy(vec!["127.0.0.1"]);
+		let client = Client::new(ApiKey::new("key")).proxy(proxy).unwrap();
+		futures::executor::block_on(async {
+			// the proxy resolves the host
+			let proxied = client.clone().base_url("http://youtube.invalid/youtube/v3");
+			proxied.videos().id("DnJgoWDxG2A").await.unwrap();
+			// hosts of the no proxy list are connected to directly
+			let direct = client.base_url(format!("http://{}", address));
+			direct.videos().id("DnJgoWDxG2A").await.unwrap();
+		});
+
+		let request = proxy_server.join().unwrap();
+		assert!(request.starts_with("get http://youtube.invalid/youtube/v3/videos?"));
+		// base64 of user:secret
+		assert!(request.contains("proxy-authorization: basic dxnlcjpzzwnyzxq="));
+		assert!(server.join().unwrap().starts_with("get /videos?"));
+	}
+}
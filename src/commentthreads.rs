@@ -0,0 +1,369 @@
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::PageInfo;
+pub use super::Error;
+use super::{
+	error::{InvalidRequest, MissingAccessToken, Serialization},
+	limits,
+	pagination::{self, Paginated},
+	request::{self, Request},
+	search::parameter_enum,
+	AccessToken, ApiKey, ChannelId, Client, Timestamp, VideoId,
+};
+
+/// request struct for the commentThreads endpoint, listing the top level
+/// comments of a video or a channel with their replies
+///
+/// Exactly one of [`video_id`](#method.video_id),
+/// [`all_threads_related_to_channel_id`](#method.all_threads_related_to_channel_id)
+/// and [`id`](#method.id) selects the threads. Held back comments can be
+/// listed by the owner of the channel with
+/// [`moderation_status`](#method.moderation_status):
+///
+/// ```rust
+/// # use yt_api::{commentthreads::{CommentThreadOrder, ModerationStatus}, AccessToken, ApiKey, Client, Error, Request};
+/// let client = Client::new(ApiKey::new("key"));
+/// let threads = client.comment_threads().video_id("DnJgoWDxG2A").search_terms("borrow checker").order(CommentThreadOrder::Time);
+/// assert!(threads.build_url().unwrap().contains("&order=time&searchTerms=borrow+checker&videoId=DnJgoWDxG2A"));
+///
+/// let held = client.comment_threads().video_id("DnJgoWDxG2A").moderation_status(ModerationStatus::HeldForReview);
+/// let error = held.build_url().unwrap_err();
+/// assert_eq!(error.to_string(), "the moderationStatus filter requires an oauth access token");
+///
+/// let held = held.access_token(AccessToken::new("token"));
+/// assert!(held.build_url().unwrap().contains("&moderationStatus=heldForReview&"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommentThreads {
+	data: CommentThreadsData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentThreadsData {
+	#[serde(skip)]
+	client: Client,
+	key: ApiKey,
+	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	all_threads_related_to_channel_id: Option<ChannelId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_results: Option<u8>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	moderation_status: Option<ModerationStatus>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	order: Option<CommentThreadOrder>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	page_token: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	search_terms: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	text_format: Option<TextFormat>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	video_id: Option<VideoId>,
+}
+
+impl CommentThreads {
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "commentThreads";
+
+	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).comment_threads()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
+		Self {
+			data: CommentThreadsData {
+				key: client.key.clone(),
+				client,
+				part: String::from("snippet"),
+				fields: None,
+				all_threads_related_to_channel_id: None,
+				id: None,
+				max_results: None,
+				moderation_status: None,
+				order: None,
+				page_token: None,
+				search_terms: None,
+				text_format: None,
+				video_id: None,
+			},
+		}
+	}
+
+	/// the threads of all videos of the channel
+	#[must_use]
+	pub fn all_threads_related_to_channel_id(mut self, channel_id: impl Into<ChannelId>) -> Self {
+		self.data.all_threads_related_to_channel_id = Some(channel_id.into());
+		self
+	}
+
+	/// the threads with the given comma separated ids
+	#[must_use]
+	pub fn id(mut self, id: impl Into<String>) -> Self {
+		self.data.id = Some(id.into());
+		self
+	}
+
+	/// the threads of the video
+	#[must_use]
+	pub fn video_id(mut self, video_id: impl Into<VideoId>) -> Self {
+		self.data.video_id = Some(video_id.into());
+		self
+	}
+
+	/// number of threads per page, at most 100
+	#[must_use]
+	pub fn max_results(mut self, max_results: impl Into<u8>) -> Self {
+		self.data.max_results = Some(max_results.into());
+		self
+	}
+
+	/// only list the threads in the given moderation state, which needs an
+	/// [`access_token`](#method.access_token) of the channel owner
+	#[must_use]
+	pub fn moderation_status(mut self, moderation_status: impl Into<ModerationStatus>) -> Self {
+		self.data.moderation_status = Some(moderation_status.into());
+		self
+	}
+
+	#[must_use]
+	pub fn order(mut self, order: impl Into<CommentThreadOrder>) -> Self {
+		self.data.order = Some(order.into());
+		self
+	}
+
+	#[must_use]
+	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
+		self.data.page_token = Some(page_token.into());
+		self
+	}
+
+	/// only list the threads containing the terms
+	#[must_use]
+	pub fn search_terms(mut self, search_terms: impl Into<String>) -> Self {
+		self.data.search_terms = Some(search_terms.into());
+		self
+	}
+
+	/// format of the `text_display` of the comments, html by default
+	#[must_use]
+	pub fn text_format(mut self, text_format: impl Into<TextFormat>) -> Self {
+		self.data.text_format = Some(text_format.into());
+		self
+	}
+
+	/// request the `replies` part with some of the replies of each thread
+	#[must_use]
+	pub fn replies(mut self) -> Self {
+		self.data.part.push_str(",replies");
+		self
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/topLevelComment/snippet/textOriginal),nextPageToken`,
+	/// fields which are not selected are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+}
+
+impl CommentThreadsData {
+	fn validate(&self) -> Result<(), Error> {
+		let filter = request::exactly_one_filter(&[
+			(
+				"allThreadsRelatedToChannelId",
+				self.all_threads_related_to_channel_id.is_some(),
+			),
+			("id", self.id.is_some()),
+			("videoId", self.video_id.is_some()),
+		])?;
+		let list_parameters = [
+			("maxResults", self.max_results.is_some()),
+			("moderationStatus", self.moderation_status.is_some()),
+			("order", self.order.is_some()),
+			("pageToken", self.page_token.is_some()),
+			("searchTerms", self.search_terms.is_some()),
+		];
+		if filter == "id" {
+			if let Some((parameter, _)) = list_parameters.iter().find(|(_, set)| *set) {
+				return InvalidRequest {
+					reason: format!("{} cannot be combined with id", parameter),
+				}
+				.fail();
+			}
+		}
+		if self.moderation_status.is_some() && self.client.access_token.is_none() {
+			return MissingAccessToken {
+				required_by: "the moderationStatus filter",
+			}
+			.fail();
+		}
+		limits::check(
+			"maxResults",
+			&limits::COMMENT_THREADS_MAX_RESULTS,
+			self.max_results.map(u16::from),
+		)
+	}
+}
+
+impl Request for CommentThreads {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.validate()?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
+}
+
+request::request_methods!(CommentThreads => Response, "youtube#commentThreadListResponse");
+
+impl Paginated for CommentThreads {
+	type Response = Response;
+
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
+}
+
+parameter_enum! {
+	/// moderation state of the listed threads, only visible to the owner of
+	/// the channel
+	ModerationStatus("moderationStatus") {
+		HeldForReview => "heldForReview",
+		LikelySpam => "likelySpam",
+		Published => "published",
+	}
+}
+
+parameter_enum! {
+	/// order of the threads, the values differ from the ones of
+	/// [`SearchOrder`](../search/enum.SearchOrder.html)
+	///
+	/// ```rust
+	/// # use yt_api::commentthreads::{CommentThreadOrder, ModerationStatus, TextFormat};
+	/// assert_eq!(CommentThreadOrder::ALL.iter().map(|order| order.as_str()).collect::<Vec<_>>(), ["relevance", "time"]);
+	/// assert!("viewCount".parse::<CommentThreadOrder>().is_err());
+	/// assert_eq!("likelyspam".parse::<ModerationStatus>().unwrap(), ModerationStatus::LikelySpam);
+	/// assert_eq!(serde_json::to_value(TextFormat::PlainText).unwrap(), "plainText");
+	/// ```
+	CommentThreadOrder("order") {
+		Relevance => "relevance",
+		Time => "time",
+	}
+}
+
+/// the former name of [`CommentThreadOrder`](enum.CommentThreadOrder.html)
+#[deprecated(note = "renamed to `CommentThreadOrder`")]
+pub type Order = CommentThreadOrder;
+
+parameter_enum! {
+	TextFormat("textFormat") {
+		Html => "html",
+		PlainText => "plainText",
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Response {
+	pub kind: String,
+	pub etag: String,
+	pub next_page_token: Option<String>,
+	pub page_info: PageInfo,
+	pub items: Vec<CommentThread>,
+}
+
+pagination::page_methods!(Response => CommentThread, without_prev_page);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CommentThread {
+	pub kind: String,
+	pub etag: String,
+	pub id: String,
+	pub snippet: ThreadSnippet,
+	pub replies: Option<Replies>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ThreadSnippet {
+	pub channel_id: Option<ChannelId>,
+	pub video_id: Option<VideoId>,
+	pub top_level_comment: Comment,
+	pub can_reply: Option<bool>,
+	pub total_reply_count: Option<u32>,
+	pub is_public: Option<bool>,
+}
+
+/// replies of a thread, which can be fewer than its `total_reply_count`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Replies {
+	pub comments: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Comment {
+	pub kind: String,
+	pub etag: String,
+	pub id: String,
+	pub snippet: CommentSnippet,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CommentSnippet {
+	pub author_display_name: Option<String>,
+	pub author_profile_image_url: Option<String>,
+	pub author_channel_url: Option<String>,
+	pub author_channel_id: Option<AuthorChannelId>,
+	pub channel_id: Option<ChannelId>,
+	pub video_id: Option<VideoId>,
+	/// the text in the requested [`TextFormat`](enum.TextFormat.html)
+	pub text_display: Option<String>,
+	/// the text as written by the author, only sent to them
+	pub text_original: Option<String>,
+	pub parent_id: Option<String>,
+	pub can_rate: Option<bool>,
+	pub viewer_rating: Option<String>,
+	pub like_count: Option<u32>,
+	pub moderation_status: Option<String>,
+	pub published_at: Option<Timestamp>,
+	pub updated_at: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AuthorChannelId {
+	pub value: Option<ChannelId>,
+}
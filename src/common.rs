@@ -0,0 +1,259 @@
+//! types shared by the responses of several endpoints
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// thumbnails of a video, channel or playlist in the sizes provided by the api
+///
+/// Every endpoint returns the same type, so the helpers work on the
+/// thumbnails of any result:
+///
+/// ```rust
+/// # use yt_api::{channels, search};
+/// let search: search::Response = serde_json::from_str(r#"{ "items": [{
+///     "snippet": { "thumbnails": { "high": { "url": "hqdefault.jpg", "width": 480, "height": 360 } } }
+/// }] }"#).unwrap();
+/// let channels: channels::Response = serde_json::from_str(r#"{ "items": [{
+///     "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw",
+///     "snippet": { "thumbnails": { "medium": { "url": "s240.jpg", "width": 240, "height": 240 } } }
+/// }] }"#).unwrap();
+///
+/// let thumbnails: Vec<&yt_api::Thumbnails> = vec![
+///     search.items[0].snippet.thumbnails.as_ref().unwrap(),
+///     channels.items[0].snippet.thumbnails.as_ref().unwrap(),
+/// ];
+/// let best = thumbnails.iter().map(|thumbnails| thumbnails.best().unwrap().url.as_str());
+/// assert_eq!(best.collect::<Vec<_>>(), ["hqdefault.jpg", "s240.jpg"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Thumbnails {
+	pub default: Option<Thumbnail>,
+	pub medium: Option<Thumbnail>,
+	pub high: Option<Thumbnail>,
+	pub standard: Option<Thumbnail>,
+	pub maxres: Option<Thumbnail>,
+}
+
+impl Thumbnails {
+	/// urls of the available thumbnails by their size name, e.g. `"high"`
+	///
+	/// ```rust
+	/// # use yt_api::Thumbnails;
+	/// let thumbnails: Thumbnails = serde_json::from_str(r#"{
+	///     "default": { "url": "https://i.ytimg.com/vi/DnJgoWDxG2A/default.jpg" },
+	///     "high": { "url": "https://i.ytimg.com/vi/DnJgoWDxG2A/hqdefault.jpg" }
+	/// }"#).unwrap();
+	///
+	/// let urls = thumbnails.url_map();
+	/// assert_eq!(urls.len(), 2);
+	/// assert_eq!(urls["high"], "https://i.ytimg.com/vi/DnJgoWDxG2A/hqdefault.jpg");
+	/// assert!(!urls.contains_key("maxres"));
+	/// ```
+	#[must_use]
+	pub fn url_map(&self) -> HashMap<&'static str, &str> {
+		[
+			("default", &self.default),
+			("medium", &self.medium),
+			("high", &self.high),
+			("standard", &self.standard),
+			("maxres", &self.maxres),
+		]
+		.iter()
+		.filter_map(|(name, thumbnail)| {
+			thumbnail
+				.as_ref()
+				.map(|thumbnail| (*name, thumbnail.url.as_str()))
+		})
+		.collect()
+	}
+
+	/// the available thumbnails from the largest to the smallest, i.e. in the
+	/// order `maxres`, `standard`, `high`, `medium` and `default`
+	pub fn iter(&self) -> impl Iterator<Item = &Thumbnail> {
+		self.by_size().into_iter().map(|(thumbnail, _)| thumbnail)
+	}
+
+	/// the largest available thumbnail
+	///
+	/// Thumbnails reporting a width or height of 0, like the placeholders of
+	/// upcoming livestreams, are only returned if there is no other one.
+	///
+	/// ```rust
+	/// # use yt_api::Thumbnails;
+	/// let thumbnails: Thumbnails = serde_json::from_str(r#"{
+	///     "default": { "url": "default.jpg", "width": 120, "height": 90 },
+	///     "high": { "url": "hqdefault.jpg", "width": 480, "height": 360 },
+	///     "maxres": { "url": "live.jpg", "width": 0, "height": 0 }
+	/// }"#).unwrap();
+	///
+	/// assert_eq!(thumbnails.best().unwrap().url, "hqdefault.jpg");
+	/// assert_eq!(thumbnails.smallest().unwrap().url, "default.jpg");
+	/// let urls = thumbnails.iter().map(|thumbnail| thumbnail.url.as_str());
+	/// assert_eq!(urls.collect::<Vec<_>>(), ["live.jpg", "hqdefault.jpg", "default.jpg"]);
+	///
+	/// let only_default: Thumbnails = serde_json::from_str(r#"{
+	///     "default": { "url": "default.jpg" }
+	/// }"#).unwrap();
+	/// assert_eq!(only_default.best().unwrap().url, "default.jpg");
+	///
+	/// let none: Thumbnails = serde_json::from_str("{}").unwrap();
+	/// assert!(none.best().is_none());
+	/// ```
+	#[must_use]
+	pub fn best(&self) -> Option<&Thumbnail> {
+		self.iter()
+			.find(|thumbnail| !thumbnail.is_degenerate())
+			.or_else(|| self.iter().next())
+	}
+
+	/// the smallest available thumbnail, like [`best`](#method.best) skipping
+	/// thumbnails with a width or height of 0 if possible
+	#[must_use]
+	pub fn smallest(&self) -> Option<&Thumbnail> {
+		self.iter()
+			.filter(|thumbnail| !thumbnail.is_degenerate())
+			.last()
+			.or_else(|| self.iter().last())
+	}
+
+	/// the smallest thumbnail which is at least `width` by `height` pixels
+	///
+	/// Thumbnails without their size, e.g. because it was not selected with
+	/// `fields`, are assumed to have the usual size of their name, like
+	/// 480x360 for `high`.
+	///
+	/// ```rust
+	/// # use yt_api::Thumbnails;
+	/// let thumbnails: Thumbnails = serde_json::from_str(r#"{
+	///     "default": { "url": "default.jpg" },
+	///     "medium": { "url": "mqdefault.jpg", "width": 320, "height": 180 },
+	///     "high": { "url": "hqdefault.jpg" }
+	/// }"#).unwrap();
+	///
+	/// assert_eq!(thumbnails.at_least(100, 80).unwrap().url, "default.jpg");
+	/// assert_eq!(thumbnails.at_least(300, 150).unwrap().url, "mqdefault.jpg");
+	/// assert_eq!(thumbnails.at_least(400, 200).unwrap().url, "hqdefault.jpg");
+	/// assert!(thumbnails.at_least(640, 480).is_none());
+	/// ```
+	#[must_use]
+	pub fn at_least(&self, width: u64, height: u64) -> Option<&Thumbnail> {
+		self.by_size()
+			.into_iter()
+			.rev()
+			.find(|(thumbnail, (usual_width, usual_height))| {
+				thumbnail.width.unwrap_or(*usual_width) >= width
+					&& thumbnail.height.unwrap_or(*usual_height) >= height
+			})
+			.map(|(thumbnail, _)| thumbnail)
+	}
+
+	/// the available thumbnails from the largest to the smallest, with the
+	/// usual width and height of their size
+	fn by_size(&self) -> Vec<(&Thumbnail, (u64, u64))> {
+		[
+			(&self.maxres, (1280, 720)),
+			(&self.standard, (640, 480)),
+			(&self.high, (480, 360)),
+			(&self.medium, (320, 180)),
+			(&self.default, (120, 90)),
+		]
+		.iter()
+		.filter_map(|(thumbnail, size)| thumbnail.as_ref().map(|thumbnail| (thumbnail, *size)))
+		.collect()
+	}
+}
+
+/// url and, if known, size of a single thumbnail
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Thumbnail {
+	pub url: String,
+	pub width: Option<u64>,
+	pub height: Option<u64>,
+}
+
+impl Thumbnail {
+	/// thumbnail at `url` of unknown size
+	#[must_use]
+	pub fn new(url: impl Into<String>) -> Self {
+		Self {
+			url: url.into(),
+			width: None,
+			height: None,
+		}
+	}
+
+	#[must_use]
+	pub fn with_size(mut self, width: u64, height: u64) -> Self {
+		self.width = Some(width);
+		self.height = Some(height);
+		self
+	}
+
+	/// whether the thumbnail reports a width or height of 0
+	fn is_degenerate(&self) -> bool {
+		self.width == Some(0) || self.height == Some(0)
+	}
+}
+
+/// number of results of a response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PageInfo {
+	/// number of results of all pages, which is an estimate of the api and
+	/// can be far off for a search, count the items of the pages for the
+	/// actual number
+	pub total_results: i64,
+	/// requested number of results per page, the last page might contain
+	/// less
+	pub results_per_page: i64,
+}
+
+/// `text` with the html entities the api leaves in titles and descriptions
+/// decoded, in a single pass so that an escaped entity like `&amp;quot;`
+/// becomes `&quot;`
+///
+/// Besides numeric entities only the named entities of the characters html
+/// escapes are decoded, unknown entities are kept.
+pub(crate) fn decode_entities(text: &str) -> String {
+	let mut decoded = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find('&') {
+		decoded.push_str(&rest[..start]);
+		rest = &rest[start..];
+		let entity = rest[1..]
+			.find(';')
+			.map(|end| &rest[1..=end])
+			.filter(|entity| entity.len() <= 10);
+		match entity.and_then(decode_entity) {
+			Some(character) => {
+				decoded.push(character);
+				rest = &rest[entity.map_or(0, str::len) + 2..];
+			}
+			None => {
+				decoded.push('&');
+				rest = &rest[1..];
+			}
+		}
+	}
+	decoded.push_str(rest);
+	decoded
+}
+
+/// character of an entity without its `&` and `;`
+fn decode_entity(entity: &str) -> Option<char> {
+	let code = match entity {
+		"amp" => return Some('&'),
+		"quot" => return Some('"'),
+		"apos" => return Some('\''),
+		"lt" => return Some('<'),
+		"gt" => return Some('>'),
+		"nbsp" => return Some('\u{a0}'),
+		_ => entity.strip_prefix('#')?,
+	};
+	let code = match code.strip_prefix('x').or_else(|| code.strip_prefix('X')) {
+		Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+		None => code.parse().ok()?,
+	};
+	std::char::from_u32(code)
+}
@@ -0,0 +1,322 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use snafu::Snafu;
+
+use super::{observer::redact, transport::TransportError};
+
+/// custom error type shared by all endpoints
+#[derive(Debug, Snafu)]
+#[snafu(visibility = "pub(crate)")]
+pub enum Error {
+	#[snafu(display("failed to connect to {}: {}", url, string))]
+	Connection { url: String, string: String },
+	/// `string` holds the beginning of the response body and `path` the
+	/// location of the value which could not be deserialized
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client, Error};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	///
+	/// match client.videos().id("DnJgoWDxG2A").await {
+	///     Ok(response) => println!("{:?}", response.items),
+	///     Err(Error::Deserialization { path, string, .. }) => eprintln!("unexpected value at {} in {}", path, string),
+	///     Err(error) => eprintln!("{}", error),
+	/// }
+	/// # });
+	/// ```
+	///
+	/// Only the first 500 characters of a large body are kept.
+	#[snafu(display(
+		"failed to deserialize {} of the response of {}: {} {}",
+		path,
+		url,
+		source,
+		string
+	))]
+	Deserialization {
+		url: String,
+		path: String,
+		string: String,
+		source: serde_json::Error,
+	},
+	#[snafu(display("failed to serialize: {}", source))]
+	Serialization {
+		source: serde_urlencoded::ser::Error,
+	},
+	#[snafu(display("invalid {}: {}", field, message))]
+	InvalidParameter { field: String, message: String },
+	/// the parameters violate a rule of the endpoint on how they may be
+	/// combined, found before anything is sent
+	#[snafu(display("invalid request: {}", reason))]
+	InvalidRequest { reason: String },
+	/// the request is not authorized with an access token although a part,
+	/// filter or endpoint of it requires one, `required_by` names which, like
+	/// `the fileDetails part`
+	#[snafu(display("{} requires an oauth access token", required_by))]
+	MissingAccessToken { required_by: String },
+	/// the `kind` of a response is not the one the endpoint returns, e.g.
+	/// because a proxy answered with the response of another endpoint
+	///
+	/// A kind left out with `fields` is not checked.
+	#[snafu(display("expected a response of the kind {} but got {}", expected, got))]
+	UnexpectedKind { expected: String, got: String },
+	/// no api key could be read, see [`ApiKeyError`]
+	#[snafu(display("invalid api key: {}", source))]
+	ApiKey { source: ApiKeyError },
+	#[snafu(display("{} responded with status {}: {}", url, status, message))]
+	Api {
+		url: String,
+		status: u16,
+		reason: Option<String>,
+		message: String,
+	},
+	#[snafu(display("the request to {} timed out after {:?}", url, duration))]
+	Timeout { url: String, duration: Duration },
+	/// the [`CancellationToken`](struct.CancellationToken.html) of the
+	/// request was cancelled
+	#[snafu(display("the request to {} was cancelled", url))]
+	Cancelled { url: String },
+	#[snafu(display(
+		"a request costing {} units exceeds the quota budget of {} with {} units already used",
+		cost,
+		budget,
+		used
+	))]
+	QuotaBudgetExceeded { used: u64, cost: u32, budget: u64 },
+	#[snafu(display("giving up after {} attempts: {}", attempts, source))]
+	Retry { attempts: u32, source: Box<Error> },
+}
+
+/// error of [`ApiKey::parse`](struct.ApiKey.html#method.parse) and
+/// [`ApiKey::from_env_var`](struct.ApiKey.html#method.from_env_var), which
+/// converts into [`Error::ApiKey`]
+#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+#[snafu(visibility = "pub(crate)")]
+pub enum ApiKeyError {
+	/// the key is empty or only whitespace
+	#[snafu(display("the api key is empty"))]
+	Empty,
+	#[snafu(display("the environment variable {} is not set", var))]
+	NotPresent { var: String },
+	#[snafu(display("the environment variable {} is not valid unicode", var))]
+	NotUnicode { var: String },
+}
+
+impl From<ApiKeyError> for Error {
+	fn from(source: ApiKeyError) -> Self {
+		Error::ApiKey { source }
+	}
+}
+
+/// error body returned by google apis for a non successful status
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+	error: ErrorBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+	message: String,
+	#[serde(default)]
+	errors: Vec<ErrorItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorItem {
+	reason: Option<String>,
+}
+
+impl Error {
+	/// maximum number of characters of a response body kept in an error
+	const MAX_BODY_CHARS: usize = 500;
+
+	/// builds an [`Error::Connection`] for the request to the redacted `url`,
+	/// the credentials are redacted from the message as well, since transport
+	/// errors usually contain the url of the request
+	pub(crate) fn connection(url: &str, transport_error: &TransportError) -> Self {
+		Error::Connection {
+			url: url.to_string(),
+			string: redact(&transport_error.to_string()),
+		}
+	}
+
+	/// builds an [`Error::Api`] from the status and body of a failed response
+	/// to the request to the redacted `url`
+	pub(crate) fn from_response(url: &str, status: u16, body: String) -> Self {
+		match serde_json::from_str::<ErrorResponse>(&body) {
+			Ok(response) => Error::Api {
+				url: url.to_string(),
+				status,
+				reason: response
+					.error
+					.errors
+					.into_iter()
+					.find_map(|error| error.reason),
+				message: response.error.message,
+			},
+			Err(_) => Error::Api {
+				url: url.to_string(),
+				status,
+				reason: None,
+				message: Self::body_excerpt(&body),
+			},
+		}
+	}
+
+	/// whether sending the request again might succeed, which is the case for
+	/// connection errors, timeouts, server errors (5xx) and when the api
+	/// reports a rate limit (429 or a `rateLimitExceeded` reason)
+	///
+	/// This is the classification used by [`RetryPolicy`](struct.RetryPolicy.html).
+	///
+	/// ```rust
+	/// # use std::time::Duration;
+	/// # use yt_api::{ApiKeyError, Error};
+	/// let api = |status, reason: Option<&str>| Error::Api {
+	///     url: String::from("https://www.googleapis.com/youtube/v3/videos"),
+	///     status,
+	///     reason: reason.map(String::from),
+	///     message: String::new(),
+	/// };
+	///
+	/// let retryable = [
+	///     Error::Connection { url: String::new(), string: String::from("connection reset") },
+	///     Error::Timeout { url: String::new(), duration: Duration::from_secs(10) },
+	///     api(500, None),
+	///     api(503, Some("backendError")),
+	///     api(429, None),
+	///     api(403, Some("rateLimitExceeded")),
+	///     api(403, Some("userRateLimitExceeded")),
+	/// ];
+	/// for error in retryable.iter() {
+	///     assert!(error.is_retryable(), "{:?}", error);
+	/// }
+	///
+	/// let permanent = [
+	///     api(400, Some("badRequest")),
+	///     api(403, Some("quotaExceeded")),
+	///     api(404, Some("videoNotFound")),
+	///     Error::Serialization { source: serde_urlencoded::ser::Error::Custom("unsupported".into()) },
+	///     Error::Deserialization {
+	///         url: String::new(),
+	///         path: String::from("."),
+	///         string: String::from("x"),
+	///         source: serde_json::from_str::<u8>("x").unwrap_err(),
+	///     },
+	///     Error::InvalidParameter { field: String::from("id"), message: String::new() },
+	///     Error::InvalidRequest { reason: String::new() },
+	///     Error::MissingAccessToken { required_by: String::from("the fileDetails part") },
+	///     Error::UnexpectedKind { expected: String::from("youtube#videoListResponse"), got: String::new() },
+	///     Error::from(ApiKeyError::Empty),
+	///     Error::QuotaBudgetExceeded { used: 100, cost: 100, budget: 150 },
+	///     Error::Cancelled { url: String::new() },
+	/// ];
+	/// for error in permanent.iter() {
+	///     assert!(!error.is_retryable(), "{:?}", error);
+	/// }
+	///
+	/// // the error of the last attempt decides
+	/// let retried = Error::Retry { attempts: 3, source: Box::new(api(503, None)) };
+	/// assert!(retried.is_retryable());
+	/// ```
+	#[must_use]
+	pub fn is_retryable(&self) -> bool {
+		match self {
+			Error::Connection { .. } | Error::Timeout { .. } => true,
+			Error::Api { status, reason, .. } => {
+				*status >= 500
+					|| *status == 429
+					|| matches!(
+						reason.as_deref(),
+						Some("rateLimitExceeded") | Some("userRateLimitExceeded")
+					)
+			}
+			Error::Retry { source, .. } => source.is_retryable(),
+			_ => false,
+		}
+	}
+
+	/// beginning of a response body with its credentials redacted, cut down to
+	/// at most [`MAX_BODY_CHARS`](#associatedconstant.MAX_BODY_CHARS)
+	/// characters
+	pub(crate) fn body_excerpt(body: &str) -> String {
+		let mut excerpt = body.chars().take(Self::MAX_BODY_CHARS).collect::<String>();
+		if excerpt.len() < body.len() {
+			excerpt.push('…');
+		}
+		redact(&excerpt)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::Error;
+	use crate::{testing::MockTransport, ApiKey, Client};
+
+	fn client(body: impl Into<String>) -> Client {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/", 200, body);
+		Client::with_transport(ApiKey::new("key"), mock)
+	}
+
+	#[test]
+	fn deserialization_error_has_the_path_of_the_value() {
+		let client = client(r#"{ "items": [{ "id": "DnJgoWDxG2A" }, { "id": null }] }"#);
+
+		match futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send()) {
+			Err(Error::Deserialization { path, .. }) => assert_eq!(path, "items[1].id"),
+			result => panic!("unexpected {:?}", result),
+		}
+	}
+
+	#[test]
+	fn deserialization_error_keeps_the_beginning_of_large_bodies() {
+		let malformed = format!(r#"{{ "items": [{{ "id": "{}" }}, ]}}"#, "x".repeat(10_000));
+		let client = client(malformed);
+
+		let error =
+			futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send()).unwrap_err();
+		match &error {
+			Error::Deserialization { string, .. } => {
+				assert_eq!(string.chars().count(), Error::MAX_BODY_CHARS + 1);
+				assert!(string.ends_with('…'));
+			}
+			error => panic!("unexpected {:?}", error),
+		}
+		assert!(error
+			.to_string()
+			.contains("trailing comma at line 1 column"));
+	}
+
+	#[test]
+	fn unexpected_kind_is_rejected() {
+		let channels = r#"{ "kind": "youtube#channelListResponse", "items": [{ "id": "UC_x5XG1OV2P6uZZ5FSM9Ttw" }] }"#;
+
+		match futures::executor::block_on(client(channels).videos().id("DnJgoWDxG2A").send()) {
+			Err(Error::UnexpectedKind { expected, got }) => {
+				assert_eq!(expected, "youtube#videoListResponse");
+				assert_eq!(got, "youtube#channelListResponse");
+			}
+			result => panic!("unexpected {:?}", result),
+		}
+		let raw = client(channels).search().q("rust").send_raw();
+		assert!(futures::executor::block_on(raw).is_err());
+		let matching = client(channels)
+			.channels()
+			.id("UC_x5XG1OV2P6uZZ5FSM9Ttw")
+			.send();
+		assert!(futures::executor::block_on(matching).is_ok());
+	}
+
+	#[test]
+	fn kind_left_out_with_fields_is_not_checked() {
+		let client = client(r#"{ "items": [] }"#);
+
+		let request = client.videos().id("DnJgoWDxG2A").fields("items(id)").send();
+		assert!(futures::executor::block_on(request).is_ok());
+	}
+}
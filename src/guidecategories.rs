@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::PageInfo;
+pub use super::Error;
+use super::{
+	error::Serialization,
+	request::{self, Request},
+	ApiKey, ChannelId, Client, LanguageCode, RegionCode,
+};
+
+/// request struct for the guideCategories endpoint
+#[derive(Debug, Clone)]
+pub struct GuideCategories {
+	data: GuideCategoriesData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GuideCategoriesData {
+	#[serde(skip)]
+	client: Client,
+	key: ApiKey,
+	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	region_code: Option<RegionCode>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	hl: Option<LanguageCode>,
+}
+
+impl GuideCategories {
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "guideCategories";
+
+	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).guide_categories()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
+		Self {
+			data: GuideCategoriesData {
+				key: client.key.clone(),
+				client,
+				part: String::from("snippet"),
+				fields: None,
+				id: None,
+				region_code: None,
+				hl: None,
+			},
+		}
+	}
+
+	#[must_use]
+	pub fn id(mut self, id: impl Into<String>) -> Self {
+		self.data.id = Some(id.into());
+		self
+	}
+
+	#[must_use]
+	pub fn region_code(mut self, region_code: impl Into<RegionCode>) -> Self {
+		self.data.region_code = Some(region_code.into());
+		self
+	}
+
+	#[must_use]
+	pub fn hl(mut self, hl: impl Into<LanguageCode>) -> Self {
+		self.data.hl = Some(hl.into());
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+}
+
+impl GuideCategoriesData {
+	fn validate(&self) -> Result<(), Error> {
+		request::exactly_one_filter(&[
+			("id", self.id.is_some()),
+			("regionCode", self.region_code.is_some()),
+		])?;
+		if let Some(region_code) = &self.region_code {
+			region_code.validate()?;
+		}
+		if let Some(hl) = &self.hl {
+			hl.validate("hl")?;
+		}
+		Ok(())
+	}
+}
+
+impl Request for GuideCategories {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.validate()?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
+}
+
+request::request_methods!(GuideCategories => Response, "youtube#guideCategoryListResponse");
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Response {
+	pub kind: String,
+	pub etag: String,
+	pub next_page_token: Option<String>,
+	pub prev_page_token: Option<String>,
+	pub page_info: PageInfo,
+	pub items: Vec<GuideCategory>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GuideCategory {
+	pub kind: String,
+	pub etag: String,
+	pub id: String,
+	pub snippet: Snippet,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snippet {
+	pub channel_id: Option<ChannelId>,
+	pub title: Option<String>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
@@ -0,0 +1,470 @@
+//! identifiers of youtube resources
+
+use std::{
+	fmt::{self, Display},
+	str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::Error;
+
+/// implements the conversions shared by all id types, `$id::parse` validates
+macro_rules! id_conversions {
+	($id:ident) => {
+		impl From<String> for $id {
+			fn from(input: String) -> Self {
+				Self::from(input.as_str())
+			}
+		}
+
+		impl FromStr for $id {
+			type Err = Error;
+
+			fn from_str(input: &str) -> Result<Self, Error> {
+				Self::parse(input)
+			}
+		}
+
+		impl Display for $id {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				f.write_str(&self.0)
+			}
+		}
+
+		impl PartialEq<str> for $id {
+			fn eq(&self, other: &str) -> bool {
+				self.0 == other
+			}
+		}
+
+		impl PartialEq<&str> for $id {
+			fn eq(&self, other: &&str) -> bool {
+				self.0 == *other
+			}
+		}
+	};
+}
+
+/// whether all characters of the id are base64url characters
+fn is_base64url(id: &str) -> bool {
+	id.chars()
+		.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// id of a video, an 11 character base64url string like `DnJgoWDxG2A`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VideoId(String);
+
+impl VideoId {
+	const LENGTH: usize = 11;
+
+	/// parse a bare video id or extract it from a video url, see
+	/// [`from_url`](#method.from_url)
+	///
+	/// ```rust
+	/// # use yt_api::VideoId;
+	/// let id = VideoId::parse("DnJgoWDxG2A").unwrap();
+	/// assert_eq!(VideoId::parse("https://youtu.be/DnJgoWDxG2A").unwrap(), id);
+	/// assert!(VideoId::parse("DnJgoWDxG2").is_err());
+	/// ```
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		let input = input.trim();
+		if input.contains('/') {
+			return Self::from_url(input);
+		}
+		if Self::is_valid(input) {
+			return Ok(Self(input.to_string()));
+		}
+		Err(Error::InvalidParameter {
+			field: String::from("id"),
+			message: format!("{:?} is neither a video id nor a video url", input),
+		})
+	}
+
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// url of the watch page like `https://www.youtube.com/watch?v=DnJgoWDxG2A`
+	///
+	/// ```rust
+	/// # use yt_api::VideoId;
+	/// let id = VideoId::parse("DnJgoWDxG2A").unwrap();
+	/// assert_eq!(id.watch_url(), "https://www.youtube.com/watch?v=DnJgoWDxG2A");
+	/// assert_eq!(id.embed_url(), "https://www.youtube.com/embed/DnJgoWDxG2A");
+	/// assert_eq!(id.short_url(), "https://youtu.be/DnJgoWDxG2A");
+	///
+	/// for url in [id.watch_url(), id.embed_url(), id.short_url()].iter() {
+	///     assert_eq!(VideoId::from_url(url).unwrap(), id);
+	/// }
+	/// ```
+	#[must_use]
+	pub fn watch_url(&self) -> String {
+		format!("https://www.youtube.com/watch?v={}", self.0)
+	}
+
+	/// url of the player for embedding the video into a webpage
+	#[must_use]
+	pub fn embed_url(&self) -> String {
+		format!("https://www.youtube.com/embed/{}", self.0)
+	}
+
+	/// shortened url for sharing the video
+	#[must_use]
+	pub fn short_url(&self) -> String {
+		format!("https://youtu.be/{}", self.0)
+	}
+
+	/// extract the id from a video url as it is shared or pasted by users
+	///
+	/// The urls `youtube.com/watch?v=`, `youtu.be/`, `youtube.com/shorts/`,
+	/// `youtube.com/embed/`, `youtube.com/live/` and `youtube.com/v/` are
+	/// recognized with or without scheme, on the `www.`, `m.` and `music.`
+	/// subdomains and on `youtube-nocookie.com`. Other query parameters, like
+	/// a timestamp or the playlist the video was played in, are ignored.
+	///
+	/// ```rust
+	/// # use yt_api::VideoId;
+	/// let urls = [
+	///     ("https://www.youtube.com/watch?v=DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube.com/watch?v=DnJgoWDxG2A&t=30", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube.com/watch?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL&v=DnJgoWDxG2A&index=2", Some("DnJgoWDxG2A")),
+	///     ("http://youtube.com/watch?feature=share&v=DnJgoWDxG2A#t=1m", Some("DnJgoWDxG2A")),
+	///     ("https://m.youtube.com/watch?v=DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     ("https://music.youtube.com/watch?v=DnJgoWDxG2A&feature=share", Some("DnJgoWDxG2A")),
+	///     ("https://youtu.be/DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     ("https://youtu.be/DnJgoWDxG2A?si=XvKJ3f9ZpWqL&t=42", Some("DnJgoWDxG2A")),
+	///     ("youtu.be/DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube.com/shorts/DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     ("https://youtube.com/shorts/DnJgoWDxG2A?feature=share", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube.com/embed/DnJgoWDxG2A?start=10", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube-nocookie.com/embed/DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube.com/live/DnJgoWDxG2A?feature=share", Some("DnJgoWDxG2A")),
+	///     ("https://www.youtube.com/v/DnJgoWDxG2A", Some("DnJgoWDxG2A")),
+	///     // not a video
+	///     ("https://www.youtube.com/", None),
+	///     ("https://www.youtube.com/playlist?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL", None),
+	///     ("https://www.youtube.com/channel/UC_x5XG1OV2P6uZZ5FSM9Ttw", None),
+	///     ("https://www.youtube.com/@GoogleDevelopers", None),
+	///     // an id of the wrong length
+	///     ("https://www.youtube.com/watch?v=DnJgoWDxG2", None),
+	///     ("https://youtu.be/DnJgoWDxG2AA", None),
+	///     // not youtube
+	///     ("https://example.com/watch?v=DnJgoWDxG2A", None),
+	///     ("https://notyoutube.com/watch?v=DnJgoWDxG2A", None),
+	///     ("DnJgoWDxG2A", None),
+	/// ];
+	///
+	/// for (url, id) in urls.iter() {
+	///     let parsed = VideoId::from_url(url).ok();
+	///     assert_eq!(parsed.as_ref().map(VideoId::as_str), *id, "{}", url);
+	/// }
+	/// ```
+	pub fn from_url(url: &str) -> Result<Self, Error> {
+		let url = url.trim();
+		let id = Url::split(url).and_then(|mut url| {
+			if url.host == "youtu.be" {
+				return url.segments.next();
+			}
+			if !url.is_youtube() {
+				return None;
+			}
+			match (url.segments.next()?, url.segments.next()) {
+				("watch", None) => url.query_value("v"),
+				("shorts", id) | ("embed", id) | ("live", id) | ("v", id) => id,
+				_ => None,
+			}
+		});
+		match id {
+			Some(id) if Self::is_valid(id) => Ok(Self(id.to_string())),
+			_ => Err(Error::InvalidParameter {
+				field: String::from("id"),
+				message: format!("{:?} is not a video url", url),
+			}),
+		}
+	}
+
+	pub(crate) fn is_valid(id: &str) -> bool {
+		id.len() == Self::LENGTH && is_base64url(id)
+	}
+}
+
+/// extracts the id if the string is a video url, otherwise the string is
+/// taken as it is, use [`VideoId::parse`](#method.parse) to validate it
+impl From<&str> for VideoId {
+	fn from(input: &str) -> Self {
+		Self::parse(input).unwrap_or_else(|_| Self(input.to_string()))
+	}
+}
+
+id_conversions!(VideoId);
+
+/// id of a channel, `UC` followed by 22 base64url characters like
+/// `UC_x5XG1OV2P6uZZ5FSM9Ttw`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChannelId(String);
+
+impl ChannelId {
+	const LENGTH: usize = 24;
+
+	/// parse a channel id
+	///
+	/// ```rust
+	/// # use yt_api::ChannelId;
+	/// assert!(ChannelId::parse("UC_x5XG1OV2P6uZZ5FSM9Ttw").is_ok());
+	/// // a video id is not a channel id
+	/// assert!(ChannelId::parse("DnJgoWDxG2A").is_err());
+	/// ```
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		let id = input.trim();
+		if id.len() == Self::LENGTH && id.starts_with("UC") && is_base64url(id) {
+			return Ok(Self(id.to_string()));
+		}
+		Err(Error::InvalidParameter {
+			field: String::from("channelId"),
+			message: format!("{:?} is not a channel id", input),
+		})
+	}
+
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// url of the page of the channel
+	///
+	/// ```rust
+	/// # use yt_api::ChannelId;
+	/// let id = ChannelId::parse("UC_x5XG1OV2P6uZZ5FSM9Ttw").unwrap();
+	/// assert_eq!(id.url(), "https://www.youtube.com/channel/UC_x5XG1OV2P6uZZ5FSM9Ttw");
+	/// ```
+	#[must_use]
+	pub fn url(&self) -> String {
+		format!("https://www.youtube.com/channel/{}", self.0)
+	}
+}
+
+/// the string is taken as it is, use [`ChannelId::parse`](#method.parse) to
+/// validate it
+impl From<&str> for ChannelId {
+	fn from(input: &str) -> Self {
+		Self(input.trim().to_string())
+	}
+}
+
+id_conversions!(ChannelId);
+
+/// id of a playlist like `PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL`
+///
+/// Playlist ids differ in prefix and length, e.g. the uploads of a channel are
+/// the playlist `UU…`, so only the character set is validated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlaylistId(String);
+
+impl PlaylistId {
+	/// parse a playlist id
+	///
+	/// ```rust
+	/// # use yt_api::PlaylistId;
+	/// assert!(PlaylistId::parse("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL").is_ok());
+	/// assert!(PlaylistId::parse("PL VvjrrRCBy2").is_err());
+	/// ```
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		let id = input.trim();
+		if id.len() >= 2 && is_base64url(id) {
+			return Ok(Self(id.to_string()));
+		}
+		Err(Error::InvalidParameter {
+			field: String::from("playlistId"),
+			message: format!("{:?} is not a playlist id", input),
+		})
+	}
+
+	/// extract the id from the `list` parameter of a playlist url, or of the
+	/// url of a video played in a playlist
+	///
+	/// ```rust
+	/// # use yt_api::PlaylistId;
+	/// let urls = [
+	///     ("https://www.youtube.com/playlist?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL", Some("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")),
+	///     ("https://m.youtube.com/playlist?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL", Some("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")),
+	///     ("https://www.youtube.com/watch?v=DnJgoWDxG2A&list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL&index=2", Some("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")),
+	///     ("https://youtu.be/DnJgoWDxG2A?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL", Some("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")),
+	///     ("https://www.youtube.com/embed/videoseries?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL", Some("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")),
+	///     ("https://www.youtube.com/watch?v=DnJgoWDxG2A", None),
+	///     ("https://www.youtube.com/playlist?list=", None),
+	///     ("https://example.com/playlist?list=PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL", None),
+	/// ];
+	///
+	/// for (url, id) in urls.iter() {
+	///     let parsed = PlaylistId::from_url(url).ok();
+	///     assert_eq!(parsed.as_ref().map(PlaylistId::as_str), *id, "{}", url);
+	/// }
+	/// ```
+	pub fn from_url(url: &str) -> Result<Self, Error> {
+		let url = url.trim();
+		Url::split(url)
+			.filter(|url| url.host == "youtu.be" || url.is_youtube())
+			.and_then(|url| url.query_value("list"))
+			.and_then(|id| Self::parse(id).ok())
+			.ok_or_else(|| Error::InvalidParameter {
+				field: String::from("playlistId"),
+				message: format!("{:?} is not a playlist url", url),
+			})
+	}
+
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+/// the string is taken as it is, use [`PlaylistId::parse`](#method.parse) to
+/// validate it
+impl From<&str> for PlaylistId {
+	fn from(input: &str) -> Self {
+		Self(input.trim().to_string())
+	}
+}
+
+id_conversions!(PlaylistId);
+
+/// reference to a channel as given by users, see
+/// [`Client::resolve_channel`](../struct.Client.html#method.resolve_channel)
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ChannelRef<'a> {
+	Id(ChannelId),
+	/// handle without the leading `@`
+	Handle(&'a str),
+	/// legacy username
+	Username(&'a str),
+	/// name which might be a handle or a legacy username
+	Name(&'a str),
+}
+
+impl<'a> ChannelRef<'a> {
+	/// recognizes channel ids, `@handles`, names and the urls
+	/// `youtube.com/channel/UC…`, `youtube.com/@handle`, `youtube.com/user/name`
+	/// and `youtube.com/c/name`
+	pub(crate) fn parse(input: &'a str) -> Result<Self, Error> {
+		let input = input.trim();
+		let reference = if input.contains('/') {
+			Url::split(input)
+				.filter(Url::is_youtube)
+				.and_then(
+					|mut url| match (url.segments.next()?, url.segments.next()) {
+						("channel", Some(id)) => ChannelId::parse(id).ok().map(ChannelRef::Id),
+						("user", Some(name)) => Some(ChannelRef::Username(name)),
+						("c", Some(name)) => Some(ChannelRef::Name(name)),
+						(handle, _) => handle.strip_prefix('@').map(ChannelRef::Handle),
+					},
+				)
+		} else if let Some(handle) = input.strip_prefix('@') {
+			Some(ChannelRef::Handle(handle))
+		} else if let Ok(id) = ChannelId::parse(input) {
+			Some(ChannelRef::Id(id))
+		} else {
+			Some(ChannelRef::Name(input))
+		};
+		match reference {
+			Some(ChannelRef::Handle(name))
+			| Some(ChannelRef::Username(name))
+			| Some(ChannelRef::Name(name))
+				if name.is_empty() =>
+			{
+				None
+			}
+			reference => reference,
+		}
+		.ok_or_else(|| Error::InvalidParameter {
+			field: String::from("channelId"),
+			message: format!(
+				"{:?} is neither a channel, a handle nor a channel url",
+				input
+			),
+		})
+	}
+}
+
+/// url split into the parts needed to find ids in it
+struct Url<'a> {
+	/// host without the `www.`, `m.` or `music.` subdomain
+	host: &'a str,
+	segments: std::str::Split<'a, char>,
+	query: &'a str,
+}
+
+impl<'a> Url<'a> {
+	const SUBDOMAINS: &'static [&'static str] = &["www.", "m.", "music."];
+	const HOSTS: &'static [&'static str] = &["youtube.com", "youtube-nocookie.com"];
+
+	/// splits an url with or without scheme, `None` if it has no path
+	fn split(url: &'a str) -> Option<Self> {
+		let url = url
+			.strip_prefix("https://")
+			.or_else(|| url.strip_prefix("http://"))
+			.unwrap_or(url);
+		let url = url.split('#').next().unwrap_or_default();
+		let (host, rest) = url.split_once('/')?;
+		let host = Self::SUBDOMAINS
+			.iter()
+			.find_map(|subdomain| host.strip_prefix(subdomain))
+			.unwrap_or(host);
+		let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+		Some(Self {
+			host,
+			segments: path.split('/'),
+			query,
+		})
+	}
+
+	fn is_youtube(&self) -> bool {
+		Self::HOSTS.contains(&self.host)
+	}
+
+	fn query_value(&self, name: &str) -> Option<&'a str> {
+		self.query.split('&').find_map(|pair| {
+			let (key, value) = pair.split_once('=')?;
+			Some(value).filter(|_| key == name)
+		})
+	}
+}
+
+/// appends the ids to the query as a comma separated list
+///
+/// The ids are percent-encoded on their own and joined with literal commas,
+/// because the api does not split a list whose commas were encoded to `%2C`
+/// and silently returns fewer results.
+pub(crate) fn append_comma_separated<T: Display>(query: &mut String, name: &str, ids: &[T]) {
+	if ids.is_empty() {
+		return;
+	}
+	let ids = ids
+		.iter()
+		.map(|id| percent_encode(&id.to_string()))
+		.collect::<Vec<_>>();
+	if !query.is_empty() {
+		query.push('&');
+	}
+	query.push_str(name);
+	query.push('=');
+	query.push_str(&ids.join(","));
+}
+
+/// percent-encodes everything but the unreserved characters of rfc 3986
+fn percent_encode(value: &str) -> String {
+	value
+		.bytes()
+		.map(|byte| match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+				char::from(byte).to_string()
+			}
+			_ => format!("%{:02X}", byte),
+		})
+		.collect()
+}
@@ -0,0 +1,81 @@
+//! location of a deserialization error within a json document
+
+/// container the scanner is currently in
+enum Frame {
+	Object { key: Option<String>, in_key: bool },
+	Array { index: usize },
+}
+
+/// path like `items[3].contentDetails.duration` of the value at `line` and
+/// `column` of `json`, as reported by a `serde_json::Error`
+///
+/// The document is only scanned up to the position, so the path is found
+/// even in documents which are invalid after it.
+pub(crate) fn path_at(json: &str, line: usize, column: usize) -> String {
+	let offset = json
+		.split_inclusive('\n')
+		.take(line.saturating_sub(1))
+		.map(str::len)
+		.sum::<usize>()
+		+ column;
+	let mut stack = Vec::new();
+	let mut bytes = json.bytes().take(offset).enumerate();
+	while let Some((start, byte)) = bytes.next() {
+		match byte {
+			b'{' => stack.push(Frame::Object {
+				key: None,
+				in_key: true,
+			}),
+			b'[' => stack.push(Frame::Array { index: 0 }),
+			b'}' | b']' => {
+				stack.pop();
+			}
+			b',' => match stack.last_mut() {
+				Some(Frame::Object { in_key, .. }) => *in_key = true,
+				Some(Frame::Array { index }) => *index += 1,
+				None => {}
+			},
+			b':' => {
+				if let Some(Frame::Object { in_key, .. }) = stack.last_mut() {
+					*in_key = false;
+				}
+			}
+			b'"' => {
+				let mut escaped = false;
+				let mut end = offset.min(json.len());
+				for (index, byte) in bytes.by_ref() {
+					match byte {
+						b'"' if !escaped => {
+							end = index;
+							break;
+						}
+						b'\\' => escaped = !escaped,
+						_ => escaped = false,
+					}
+				}
+				if let Some(Frame::Object { key, in_key: true }) = stack.last_mut() {
+					*key = json.get(start + 1..end).map(str::to_string);
+				}
+			}
+			_ => {}
+		}
+	}
+
+	let mut path = String::new();
+	for frame in &stack {
+		match frame {
+			Frame::Object { key: Some(key), .. } => {
+				if !path.is_empty() {
+					path.push('.');
+				}
+				path.push_str(key);
+			}
+			Frame::Object { key: None, .. } => {}
+			Frame::Array { index } => path.push_str(&format!("[{}]", index)),
+		}
+	}
+	if path.is_empty() {
+		path.push('.');
+	}
+	path
+}
@@ -6,7 +6,7 @@
 //!
 //! To perform a search query, you can create a [`SearchList`][search_list] query.
 //!
-//! ```rust
+//! ```rust,no_run
 //! # use yt_api::{
 //! #     search::SearchList,
 //! #     ApiKey,
@@ -17,19 +17,231 @@
 //! # });
 //! ```
 //!
+//! ## Sharing settings between requests
+//!
+//! A [`Client`][client] holds the api key and settings like the base url or a
+//! retry policy, and creates requests which use them.
+//!
+//! ```rust,no_run
+//! # use yt_api::{ApiKey, Client};
+//! #
+//! # futures::executor::block_on(async {
+//! let client = Client::new(ApiKey::new("your-youtube-api-key"));
+//! let result = client.videos().id("DnJgoWDxG2A").await;
+//! # });
+//! ```
+//!
+//! ## Reusing requests
+//!
+//! Requests are plain builders which are only sent once they are awaited, or
+//! explicitly with `send`, so a request can be cloned and sent again.
+//!
+//! ```rust,no_run
+//! # use yt_api::{ApiKey, Client};
+//! #
+//! # futures::executor::block_on(async {
+//! let client = Client::new(ApiKey::new("your-youtube-api-key"));
+//! let request = client.videos().id("DnJgoWDxG2A");
+//! let mut result = request.clone().await;
+//! if result.is_err() {
+//!     result = request.send().await;
+//! }
+//! # });
+//! ```
+//!
 //! [search_list]: ./search/struct.SearchList.html
 //! [search_perform]: ./search/struct.SearchList.html#method.perform
+//! [client]: ./struct.Client.html
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cancel;
+pub mod channels;
+mod client;
+pub mod commentthreads;
+mod common;
+mod error;
+pub mod guidecategories;
+mod ids;
+mod json_path;
+pub mod limits;
+mod locale;
+pub mod members;
+pub mod membershipslevels;
+pub mod observer;
+pub mod pagination;
 pub mod playlistitems;
+pub mod playlists;
+mod proxy;
+mod published;
+mod ratelimit;
+mod request;
+mod retry;
 pub mod search;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod transport;
+pub mod videocategories;
 pub mod videos;
-use serde::Serialize;
+use std::{fmt, sync::Arc};
+
+use serde::{Serialize, Serializer};
 
-#[derive(Debug, Clone, Serialize)]
-pub struct ApiKey(String);
+pub use cancel::CancellationToken;
+pub use client::Client;
+pub use common::{PageInfo, Thumbnail, Thumbnails};
+pub use error::{ApiKeyError, Error};
+pub use ids::{ChannelId, PlaylistId, VideoId};
+pub use locale::{LanguageCode, Localized, RegionCode};
+pub use proxy::Proxy;
+#[cfg(feature = "chrono")]
+pub use published::Published;
+pub use published::Timestamp;
+pub use request::{Request, ResponseFuture};
+pub use retry::RetryPolicy;
+
+/// root of the youtube data api that requests are sent to by default
+pub const BASE_URL: &str = "https://www.googleapis.com/youtube/v3";
+
+/// api key of a google cloud project
+///
+/// The key is redacted from the debug output, from the urls which are logged
+/// and from error messages.
+///
+/// ```rust
+/// # use yt_api::ApiKey;
+/// let key = ApiKey::new("AIzaSecret");
+/// assert_eq!(format!("{:?}", key), "ApiKey(****)");
+/// ```
+///
+/// The key is shared instead of copied, cloning it for every request of a
+/// [`Client`](struct.Client.html) does not allocate.
+#[derive(Clone)]
+pub struct ApiKey(Arc<str>);
 
 impl ApiKey {
+	/// environment variable read by [`from_env`](#method.from_env)
+	pub const ENV_VAR: &'static str = "YT_API_KEY";
+
+	/// key without the whitespace around it, like the newline at the end of
+	/// a file the key was read from
+	///
+	/// An empty key is only rejected by the api, [`parse`](#method.parse)
+	/// rejects it right away.
 	pub fn new(key: impl Into<String>) -> Self {
-		Self(key.into())
+		Self(Arc::from(key.into().trim()))
+	}
+
+	/// key without the whitespace around it, failing with
+	/// [`ApiKeyError::Empty`](enum.ApiKeyError.html#variant.Empty) if
+	/// nothing is left
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, ApiKeyError, Client};
+	/// let key = ApiKey::parse("AIzaKey\n").unwrap();
+	/// let url = yt_api::Request::build_url(&Client::new(key).videos().id("DnJgoWDxG2A")).unwrap();
+	/// assert!(url.contains("key=AIzaKey&"));
+	///
+	/// assert_eq!(ApiKey::parse(" \n").unwrap_err(), ApiKeyError::Empty);
+	/// ```
+	pub fn parse(key: &str) -> Result<Self, ApiKeyError> {
+		let key = key.trim();
+		if key.is_empty() {
+			return Err(ApiKeyError::Empty);
+		}
+		Ok(Self(Arc::from(key)))
+	}
+
+	/// key of the `YT_API_KEY` environment variable
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, ApiKeyError};
+	/// std::env::set_var("YT_API_KEY", "AIzaKey\n");
+	/// assert!(ApiKey::from_env().is_ok());
+	///
+	/// std::env::set_var("YT_API_KEY", "");
+	/// assert_eq!(ApiKey::from_env().unwrap_err(), ApiKeyError::Empty);
+	///
+	/// std::env::remove_var("YT_API_KEY");
+	/// let error = ApiKey::from_env().unwrap_err();
+	/// assert_eq!(error, ApiKeyError::NotPresent { var: String::from("YT_API_KEY") });
+	/// ```
+	pub fn from_env() -> Result<Self, ApiKeyError> {
+		Self::from_env_var(Self::ENV_VAR)
+	}
+
+	/// key of the environment variable `name`, see [`parse`](#method.parse)
+	pub fn from_env_var(name: &str) -> Result<Self, ApiKeyError> {
+		match std::env::var(name) {
+			Ok(key) => Self::parse(&key),
+			Err(std::env::VarError::NotPresent) => Err(ApiKeyError::NotPresent {
+				var: name.to_string(),
+			}),
+			Err(std::env::VarError::NotUnicode(_)) => Err(ApiKeyError::NotUnicode {
+				var: name.to_string(),
+			}),
+		}
+	}
+
+	/// whether the api accepts the key, see
+	/// [`Client::verify_key`](struct.Client.html#method.verify_key)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	pub async fn verify(&self) -> Result<bool, Error> {
+		Client::new(self.clone()).verify_key().await
+	}
+}
+
+impl Serialize for ApiKey {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.0)
+	}
+}
+
+impl fmt::Debug for ApiKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("ApiKey(****)")
+	}
+}
+
+/// OAuth 2.0 access token, needed for parts and filters that are only
+/// available to the authorized owner of a resource
+#[derive(Clone)]
+pub struct AccessToken(String);
+
+impl AccessToken {
+	pub fn new(token: impl Into<String>) -> Self {
+		Self(token.into())
+	}
+
+	pub(crate) fn bearer(&self) -> String {
+		format!("Bearer {}", self.0)
+	}
+}
+
+impl fmt::Debug for AccessToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("AccessToken(****)")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::{testing::MockTransport, ApiKey, Client};
+
+	#[test]
+	fn api_key_is_redacted_from_errors() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect_error(
+			"/videos?",
+			"error sending request for url (https://www.googleapis.com/youtube/v3/videos?key=AIzaSecret&part=snippet)",
+		);
+		let client = Client::with_transport(ApiKey::new("AIzaSecret"), mock);
+
+		let error =
+			futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send()).unwrap_err();
+		assert!(!error.to_string().contains("AIzaSecret"));
+		assert!(!format!("{:?}", error).contains("AIzaSecret"));
 	}
 }
@@ -0,0 +1,118 @@
+//! ranges of the numeric parameters accepted by the api, which are checked
+//! when the url of a request is built, so a value out of range fails with
+//! [`Error::InvalidParameter`](../enum.Error.html#variant.InvalidParameter)
+//! before any quota is spent
+//!
+//! ```rust
+//! # use yt_api::{limits, AccessToken, ApiKey, Client, Error, Request};
+//! assert!(limits::MAX_RESULTS.contains(&50));
+//!
+//! let client = Client::new(ApiKey::new("key"));
+//! match client.search().q("rust lang").max_results(200).build_url() {
+//!     Err(Error::InvalidParameter { field, message }) => {
+//!         assert_eq!(field, "maxResults");
+//!         assert_eq!(message, "must be in 0..=50, got 200");
+//!     }
+//!     result => panic!("unexpected {:?}", result),
+//! }
+//! assert!(client.videos().most_popular().max_results(0).build_url().is_err());
+//! let owner = client.clone().access_token(AccessToken::new("token"));
+//! assert!(owner.members().max_results(1000u16).build_url().is_ok());
+//! assert!(owner.members().max_results(1001u16).build_url().is_err());
+//! ```
+//!
+//! The `locationRadius` of a search is a distance in `m`, `km`, `ft` or `mi`:
+//!
+//! ```rust
+//! # use yt_api::{search::VideoLocation, ApiKey, Client, Request};
+//! let client = Client::new(ApiKey::new("key"));
+//! let googleplex = VideoLocation::new(37.42307, -122.08427);
+//! let search = |radius| client.search().location(googleplex.clone()).location_radius(radius);
+//!
+//! for radius in ["1500m", "10km", "0.5mi", "5000ft", "1000km"].iter() {
+//!     assert!(search(*radius).build_url().is_ok(), "{}", radius);
+//! }
+//! for radius in ["1001km", "700mi", "10", "ten km"].iter() {
+//!     assert!(search(*radius).build_url().is_err(), "{}", radius);
+//! }
+//! ```
+
+use std::{fmt::Display, ops::RangeInclusive};
+
+use super::Error;
+
+/// `maxResults` of the channels, playlistItems, playlists and search
+/// endpoints
+pub const MAX_RESULTS: RangeInclusive<u16> = 0..=50;
+
+/// `maxResults` of the mostPopular chart of the videos endpoint
+pub const VIDEOS_MAX_RESULTS: RangeInclusive<u16> = 1..=50;
+
+/// `maxResults` of the commentThreads endpoint
+pub const COMMENT_THREADS_MAX_RESULTS: RangeInclusive<u16> = 1..=100;
+
+/// `maxResults` of the members endpoint
+pub const MEMBERS_MAX_RESULTS: RangeInclusive<u16> = 0..=1000;
+
+/// latitude of the `location` of a search in degrees
+pub const LATITUDE: RangeInclusive<f32> = -90.0..=90.0;
+
+/// longitude of the `location` of a search in degrees
+pub const LONGITUDE: RangeInclusive<f32> = -180.0..=180.0;
+
+/// `locationRadius` of a search in kilometers
+pub const LOCATION_RADIUS_KM: RangeInclusive<f64> = 0.0..=1000.0;
+
+/// fails with [`Error::InvalidParameter`] if `value` is set and out of `range`
+pub(crate) fn check<T>(
+	field: &str,
+	range: &RangeInclusive<T>,
+	value: Option<T>,
+) -> Result<(), Error>
+where
+	T: PartialOrd + Display,
+{
+	match value {
+		Some(value) if !range.contains(&value) => Err(Error::InvalidParameter {
+			field: field.to_string(),
+			message: format!(
+				"must be in {}..={}, got {}",
+				range.start(),
+				range.end(),
+				value
+			),
+		}),
+		_ => Ok(()),
+	}
+}
+
+/// fails with [`Error::InvalidParameter`] unless `radius` is a distance in
+/// `m`, `km`, `ft` or `mi` within [`LOCATION_RADIUS_KM`]
+pub(crate) fn check_location_radius(radius: &str) -> Result<(), Error> {
+	const UNITS_KM: [(&str, f64); 4] = [
+		("km", 1.0),
+		("m", 0.001),
+		("ft", 0.0003048),
+		("mi", 1.609344),
+	];
+
+	let kilometers = UNITS_KM.iter().find_map(|(unit, factor)| {
+		let distance = radius.strip_suffix(unit)?.trim().parse::<f64>().ok()?;
+		Some(distance * factor)
+	});
+	match kilometers {
+		Some(kilometers) if LOCATION_RADIUS_KM.contains(&kilometers) => Ok(()),
+		Some(_) => Err(Error::InvalidParameter {
+			field: String::from("locationRadius"),
+			message: format!(
+				"must be at most {}km, got {}",
+				LOCATION_RADIUS_KM.end(),
+				radius
+			),
+		}),
+		None => Err(Error::InvalidParameter {
+			field: String::from("locationRadius"),
+			message: format!("{:?} is not a distance like 10km", radius),
+		}),
+	}
+}
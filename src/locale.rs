@@ -0,0 +1,146 @@
+//! region and language codes accepted by the api
+
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use super::Error;
+
+/// ISO 3166-1 alpha-2 country code like `US`
+///
+/// Only the format is checked, not whether the country exists, so codes which
+/// are valid but uncommon are never rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RegionCode(String);
+
+impl RegionCode {
+	/// parse a two letter region code, which is normalized to upper case
+	///
+	/// ```rust
+	/// # use yt_api::RegionCode;
+	/// assert_eq!(RegionCode::parse("de").unwrap().as_str(), "DE");
+	/// assert!(RegionCode::parse("USA").is_err());
+	/// ```
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		let region_code = Self(input.trim().to_ascii_uppercase());
+		region_code.validate()?;
+		Ok(region_code)
+	}
+
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// fails with [`Error::InvalidParameter`] unless the code consists of
+	/// two ascii letters
+	pub(crate) fn validate(&self) -> Result<(), Error> {
+		if self.0.len() == 2 && self.0.chars().all(|c| c.is_ascii_alphabetic()) {
+			return Ok(());
+		}
+		Err(Error::InvalidParameter {
+			field: String::from("regionCode"),
+			message: format!("{:?} is not a two letter region code", self.0),
+		})
+	}
+}
+
+/// the code is validated when the request is sent, use
+/// [`RegionCode::parse`](#method.parse) to validate it right away
+impl From<&str> for RegionCode {
+	fn from(input: &str) -> Self {
+		Self(input.trim().to_ascii_uppercase())
+	}
+}
+
+impl From<String> for RegionCode {
+	fn from(input: String) -> Self {
+		Self::from(input.as_str())
+	}
+}
+
+impl Display for RegionCode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+/// BCP-47 language tag like `en`, `fil` or `zh-Hant`
+///
+/// Only the basic shape is checked: a primary language of two to eight
+/// letters, optionally followed by subtags of one to eight letters or digits
+/// separated by `-`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LanguageCode(String);
+
+impl LanguageCode {
+	/// parse a language tag
+	///
+	/// ```rust
+	/// # use yt_api::LanguageCode;
+	/// assert!(LanguageCode::parse("es-419").is_ok());
+	/// assert!(LanguageCode::parse("english").is_ok());
+	/// assert!(LanguageCode::parse("en_US!").is_err());
+	/// ```
+	pub fn parse(input: &str) -> Result<Self, Error> {
+		let language_code = Self::from(input);
+		language_code.validate("hl")?;
+		Ok(language_code)
+	}
+
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// fails with [`Error::InvalidParameter`] for the parameter `field` unless
+	/// the code looks like a language tag
+	pub(crate) fn validate(&self, field: &str) -> Result<(), Error> {
+		let mut subtags = self.0.split('-');
+		let primary = subtags.next().unwrap_or_default();
+		let primary_valid =
+			(2..=8).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic());
+		let subtags_valid = subtags.all(|subtag| {
+			(1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+		});
+		if primary_valid && subtags_valid {
+			return Ok(());
+		}
+		Err(Error::InvalidParameter {
+			field: field.to_string(),
+			message: format!("{:?} is not a language code", self.0),
+		})
+	}
+}
+
+/// the code is validated when the request is sent, use
+/// [`LanguageCode::parse`](#method.parse) to validate it right away
+impl From<&str> for LanguageCode {
+	fn from(input: &str) -> Self {
+		Self(input.trim().to_string())
+	}
+}
+
+impl From<String> for LanguageCode {
+	fn from(input: String) -> Self {
+		Self::from(input.as_str())
+	}
+}
+
+impl Display for LanguageCode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+/// title and description of a resource translated into one language, either
+/// the `localized` field of a snippet requested with an `hl` parameter or an
+/// entry of the `localizations` part
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Localized {
+	pub title: Option<String>,
+	pub description: Option<String>,
+}
@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::PageInfo;
+pub use super::Error;
+use super::{
+	error::{MissingAccessToken, Serialization},
+	limits,
+	pagination::{self, Paginated},
+	request::{self, Request},
+	AccessToken, ApiKey, ChannelId, Client, Timestamp,
+};
+
+/// request struct for the members endpoint, listing the members of the
+/// channel which authorized the request
+///
+/// The endpoint is only available to the channel owner, so the request fails
+/// with [`Error::MissingAccessToken`](../enum.Error.html#variant.MissingAccessToken)
+/// unless it is authorized with an [`access_token`](#method.access_token).
+#[derive(Debug, Clone)]
+pub struct Members {
+	data: MembersData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MembersData {
+	#[serde(skip)]
+	client: Client,
+	key: ApiKey,
+	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	mode: Option<Mode>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	has_access_to_level: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	filter_by_member_channel_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_results: Option<u16>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	page_token: Option<String>,
+}
+
+impl Members {
+	const QUOTA_COST: u32 = 2;
+	const PATH: &'static str = "members";
+
+	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).members()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
+		Self {
+			data: MembersData {
+				key: client.key.clone(),
+				client,
+				part: String::from("snippet"),
+				fields: None,
+				mode: None,
+				has_access_to_level: None,
+				filter_by_member_channel_id: None,
+				max_results: None,
+				page_token: None,
+			},
+		}
+	}
+
+	/// list all current members or only the changes since the page token
+	#[must_use]
+	pub fn mode(mut self, mode: impl Into<Mode>) -> Self {
+		self.data.mode = Some(mode.into());
+		self
+	}
+
+	/// only list members with access to the memberships level with the given
+	/// id, see [`MembershipsLevels`](../membershipslevels/struct.MembershipsLevels.html)
+	#[must_use]
+	pub fn memberships_level(mut self, memberships_level: impl Into<String>) -> Self {
+		self.data.has_access_to_level = Some(memberships_level.into());
+		self
+	}
+
+	/// only list the members with the given comma separated channel ids
+	#[must_use]
+	pub fn filter_by_member_channel_id(mut self, channel_ids: impl Into<String>) -> Self {
+		self.data.filter_by_member_channel_id = Some(channel_ids.into());
+		self
+	}
+
+	/// number of members per page, at most 1000
+	#[must_use]
+	pub fn max_results(mut self, max_results: impl Into<u16>) -> Self {
+		self.data.max_results = Some(max_results.into());
+		self
+	}
+
+	#[must_use]
+	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
+		self.data.page_token = Some(page_token.into());
+		self
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	/// of the channel owner
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+}
+
+impl MembersData {
+	fn check_authorization(&self) -> Result<(), Error> {
+		match self.client.access_token {
+			Some(_) => Ok(()),
+			None => MissingAccessToken {
+				required_by: format!("the {} endpoint", Members::PATH),
+			}
+			.fail(),
+		}
+	}
+}
+
+impl Request for Members {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.check_authorization()?;
+		limits::check("maxResults", &limits::MEMBERS_MAX_RESULTS, data.max_results)?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
+}
+
+request::request_methods!(Members => Response, "youtube#memberListResponse");
+
+impl Paginated for Members {
+	type Response = Response;
+
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+	/// all current members, ordered from the oldest to the newest
+	AllCurrent,
+	/// only the members which joined or changed their level since the
+	/// previous page, for polling with the `next_page_token` of the last
+	/// response
+	Updates,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Response {
+	pub kind: String,
+	pub etag: String,
+	pub next_page_token: Option<String>,
+	pub page_info: PageInfo,
+	pub items: Vec<Member>,
+}
+
+pagination::page_methods!(Response => Member, without_prev_page);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Member {
+	pub kind: String,
+	pub etag: String,
+	pub snippet: Snippet,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snippet {
+	pub creator_channel_id: Option<ChannelId>,
+	pub member_details: MemberDetails,
+	pub memberships_details: MembershipsDetails,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MemberDetails {
+	pub channel_id: Option<ChannelId>,
+	pub channel_url: Option<String>,
+	pub display_name: Option<String>,
+	pub profile_image_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MembershipsDetails {
+	/// id of the highest memberships level the member has access to
+	pub highest_accessible_level: Option<String>,
+	pub highest_accessible_level_display_name: Option<String>,
+	pub accessible_levels: Vec<String>,
+	pub memberships_duration: Option<MembershipsDuration>,
+	pub memberships_duration_at_levels: Vec<MembershipsDurationAtLevel>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MembershipsDuration {
+	pub member_since: Option<Timestamp>,
+	pub member_total_duration_months: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MembershipsDurationAtLevel {
+	pub level: Option<String>,
+	pub member_since: Option<Timestamp>,
+	pub member_total_duration_months: Option<u32>,
+}
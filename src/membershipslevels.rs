@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::Error;
+use super::{
+	error::{MissingAccessToken, Serialization},
+	request::{self, Request},
+	AccessToken, ApiKey, ChannelId, Client,
+};
+
+/// request struct for the membershipsLevels endpoint, listing the memberships
+/// levels of the channel which authorized the request
+///
+/// The endpoint is only available to the channel owner, so the request fails
+/// with [`Error::MissingAccessToken`](../enum.Error.html#variant.MissingAccessToken)
+/// unless it is authorized with an [`access_token`](#method.access_token).
+#[derive(Debug, Clone)]
+pub struct MembershipsLevels {
+	data: MembershipsLevelsData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MembershipsLevelsData {
+	#[serde(skip)]
+	client: Client,
+	key: ApiKey,
+	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+}
+
+impl MembershipsLevels {
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "membershipsLevels";
+
+	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).memberships_levels()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
+		Self {
+			data: MembershipsLevelsData {
+				key: client.key.clone(),
+				client,
+				part: String::from("id,snippet"),
+				fields: None,
+			},
+		}
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	/// of the channel owner
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+}
+
+impl MembershipsLevelsData {
+	fn check_authorization(&self) -> Result<(), Error> {
+		match self.client.access_token {
+			Some(_) => Ok(()),
+			None => MissingAccessToken {
+				required_by: format!("the {} endpoint", MembershipsLevels::PATH),
+			}
+			.fail(),
+		}
+	}
+}
+
+impl Request for MembershipsLevels {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.check_authorization()?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
+}
+
+request::request_methods!(MembershipsLevels => Response, "youtube#membershipsLevelListResponse");
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Response {
+	pub kind: String,
+	pub etag: String,
+	pub items: Vec<MembershipsLevel>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MembershipsLevel {
+	pub kind: String,
+	pub etag: String,
+	pub id: String,
+	pub snippet: Snippet,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snippet {
+	pub creator_channel_id: Option<ChannelId>,
+	pub level_details: LevelDetails,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LevelDetails {
+	pub display_name: Option<String>,
+}
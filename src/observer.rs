@@ -0,0 +1,229 @@
+//! hooks to observe the requests sent to the api, e.g. for metrics
+//!
+//! With the `tracing` feature every request runs in a `yt_api.request` span
+//! with the `endpoint`, `method`, `parameters` and `page_token` fields, which
+//! has events for the start of each attempt, the status and size of the
+//! response, the deserialization and the retries. The credentials are
+//! redacted from all fields.
+//!
+//! ```rust
+//! # use std::{sync::{atomic::{AtomicUsize, Ordering}, Arc}, time::Duration};
+//! # use yt_api::{observer::RequestObserver, ApiKey, Client};
+//! #[derive(Debug, Default)]
+//! struct Metrics {
+//!     requests: AtomicUsize,
+//!     errors: AtomicUsize,
+//! }
+//!
+//! impl RequestObserver for Metrics {
+//!     fn on_request(&self, _: &str) {
+//!         self.requests.fetch_add(1, Ordering::Relaxed);
+//!     }
+//!
+//!     fn on_response(&self, status: u16, _: Duration) {
+//!         if status >= 400 {
+//!             self.errors.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     }
+//! }
+//!
+//! let metrics = Arc::new(Metrics::default());
+//! let client = Client::new(ApiKey::new("your-youtube-api-key")).observer(metrics.clone());
+//! // or for a single request
+//! let request = client.videos().id("DnJgoWDxG2A").with_observer(metrics);
+//! ```
+
+use std::{fmt::Debug, time::Duration};
+
+use log::debug;
+
+/// receives events about every http request sent to the api
+///
+/// The url passed to [`on_request`](#tymethod.on_request) has the api key
+/// redacted. When a request is retried, both methods are called for every
+/// attempt.
+pub trait RequestObserver: Debug + Send + Sync {
+	/// called right before the request is sent
+	fn on_request(&self, url_redacted: &str);
+
+	/// called when the response was received, before it is deserialized
+	fn on_response(&self, status: u16, elapsed: Duration);
+}
+
+/// default [`RequestObserver`](trait.RequestObserver.html), logging each
+/// request at debug level
+#[derive(Debug, Clone, Default)]
+pub struct LogObserver;
+
+impl RequestObserver for LogObserver {
+	fn on_request(&self, url_redacted: &str) {
+		debug!("getting {}", url_redacted);
+	}
+
+	fn on_response(&self, status: u16, elapsed: Duration) {
+		debug!("received status {} after {:?}", status, elapsed);
+	}
+}
+
+/// replaces the values of the credential parameters of all urls in `text`,
+/// e.g. in the message of a transport error
+pub(crate) fn redact(text: &str) -> String {
+	const CREDENTIALS: &[&str] = &["key", "access_token"];
+
+	let mut redacted = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(index) = rest.find(['?', '&']) {
+		let (before, after) = rest.split_at(index + 1);
+		redacted.push_str(before);
+		rest = after;
+		let credential = CREDENTIALS.iter().find_map(|name| {
+			let value = rest.strip_prefix(name)?.strip_prefix('=')?;
+			Some((name, value))
+		});
+		if let Some((name, value)) = credential {
+			let end = value
+				.find(|c: char| !(c.is_ascii_alphanumeric() || "-_.~%".contains(c)))
+				.unwrap_or(value.len());
+			redacted.push_str(name);
+			redacted.push_str("=…redacted…");
+			rest = &value[end..];
+		}
+	}
+	redacted.push_str(rest);
+	redacted
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		sync::{Arc, Mutex},
+		time::Duration,
+	};
+
+	use super::RequestObserver;
+	use crate::{testing::MockTransport, ApiKey, Client};
+
+	/// records the urls and the statuses and elapsed times of the responses
+	#[derive(Debug, Default)]
+	struct Recorder {
+		requests: Mutex<Vec<String>>,
+		responses: Mutex<Vec<(u16, Duration)>>,
+	}
+
+	impl RequestObserver for Recorder {
+		fn on_request(&self, url_redacted: &str) {
+			self.requests.lock().unwrap().push(url_redacted.to_string());
+		}
+
+		fn on_response(&self, status: u16, elapsed: Duration) {
+			self.responses.lock().unwrap().push((status, elapsed));
+		}
+	}
+
+	#[test]
+	fn observer_receives_the_redacted_url_and_the_status() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#);
+		let client = Client::with_transport(ApiKey::new("AIzaSecret"), mock.clone());
+		let recorder = Arc::new(Recorder::default());
+
+		let request = client
+			.videos()
+			.id("DnJgoWDxG2A")
+			.with_observer(recorder.clone());
+		futures::executor::block_on(request.send()).unwrap();
+		mock.assert_done();
+
+		let requests = recorder.requests.lock().unwrap();
+		assert_eq!(requests.len(), 1);
+		assert!(requests[0].contains("/videos?"));
+		assert!(!requests[0].contains("AIzaSecret"));
+		assert!(requests[0].contains("key=…redacted…"));
+		let responses = recorder.responses.lock().unwrap();
+		assert_eq!(responses.len(), 1);
+		let (status, elapsed) = responses[0];
+		assert_eq!(status, 200);
+		assert!(elapsed < Duration::from_secs(60));
+	}
+
+	#[cfg(feature = "tracing")]
+	mod request_span {
+		use std::{
+			fmt::Debug,
+			sync::{Arc, Mutex},
+		};
+
+		use tracing::{
+			field::{Field, Visit},
+			span, Event, Metadata, Subscriber,
+		};
+
+		use crate::{testing::MockTransport, ApiKey, Client};
+
+		/// collects the names and fields of all spans and events as lines
+		#[derive(Default)]
+		struct Collector(Arc<Mutex<Vec<String>>>);
+
+		struct Line(String);
+
+		impl Visit for Line {
+			fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+				self.0 += &format!(" {}={:?}", field.name(), value);
+			}
+		}
+
+		impl Subscriber for Collector {
+			fn enabled(&self, _: &Metadata<'_>) -> bool {
+				true
+			}
+
+			fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+				let mut line = Line(span.metadata().name().to_string());
+				span.record(&mut line);
+				self.0.lock().unwrap().push(line.0);
+				span::Id::from_u64(1)
+			}
+
+			fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+			fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+			fn event(&self, event: &Event<'_>) {
+				let mut line = Line(String::from("event"));
+				event.record(&mut line);
+				self.0.lock().unwrap().push(line.0);
+			}
+
+			fn enter(&self, _: &span::Id) {}
+
+			fn exit(&self, _: &span::Id) {}
+		}
+
+		#[test]
+		fn request_span_has_redacted_fields() {
+			let mock = Arc::new(MockTransport::new());
+			mock.expect("/playlistItems?", 200, r#"{ "items": [] }"#);
+			let client = Client::with_transport(ApiKey::new("secret-key"), mock);
+			let collector = Collector::default();
+			let lines = collector.0.clone();
+
+			tracing::subscriber::with_default(collector, || {
+				let request = client
+					.playlist_items()
+					.playlist_id("PL0")
+					.page_token("CAUQAA");
+				futures::executor::block_on(request.send()).unwrap();
+			});
+
+			let lines = lines.lock().unwrap();
+			assert!(
+				lines[0].starts_with("yt_api.request endpoint=\"playlistItems\" method=\"GET\"")
+			);
+			assert!(lines[0].ends_with("page_token=\"CAUQAA\""));
+			assert!(lines
+				.iter()
+				.any(|line| line.contains("status=200 bytes=15")));
+			assert!(lines.iter().all(|line| !line.contains("secret-key")));
+		}
+	}
+}
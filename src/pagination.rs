@@ -0,0 +1,343 @@
+//! pagination shared by all endpoints returning multiple pages
+//!
+//! ```no_run
+//! # use futures::{pin_mut, StreamExt};
+//! # use yt_api::{pagination::Paginated, playlistitems::PlaylistItems, ApiKey};
+//! #
+//! # futures::executor::block_on(async {
+//! let items = PlaylistItems::new(ApiKey::new("your-youtube-api-key"))
+//!     .playlist_id("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")
+//!     .max_results(50)
+//!     .pages()
+//!     .take_pages(3)
+//!     .items();
+//! pin_mut!(items);
+//!
+//! while let Some(item) = items.next().await {
+//!     println!("{:?}", item.unwrap().snippet.title);
+//! }
+//! # });
+//! ```
+
+use std::{
+	future::IntoFuture,
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+use futures::{
+	stream::{self, Stream, TryStreamExt},
+	FutureExt,
+};
+
+use super::{Error, PageInfo, ResponseFuture};
+
+/// response of an endpoint which might be continued on a next page
+///
+/// Implemented by the responses of all paginated endpoints, so code can be
+/// written for any of them:
+///
+/// ```rust
+/// # use yt_api::{pagination::Page, search, videos};
+/// fn summary<P: Page>(page: &P) -> String {
+///     format!("{} of about {}", page.items().len(), page.page_info().total_results)
+/// }
+///
+/// let search: search::Response = serde_json::from_str(r#"{
+///     "pageInfo": { "totalResults": 1000000, "resultsPerPage": 5 },
+///     "items": [{}, {}, {}, {}, {}]
+/// }"#).unwrap();
+/// let videos: videos::Response = serde_json::from_str(r#"{
+///     "pageInfo": { "totalResults": 1, "resultsPerPage": 1 },
+///     "items": [{ "id": "DnJgoWDxG2A" }]
+/// }"#).unwrap();
+///
+/// assert_eq!(summary(&search), "5 of about 1000000");
+/// assert_eq!(summary(&videos), "1 of about 1");
+/// ```
+pub trait Page {
+	type Item;
+
+	fn next_page_token(&self) -> Option<&str>;
+
+	/// token of the previous page, `None` on the first page and for
+	/// endpoints which only page forward
+	fn prev_page_token(&self) -> Option<&str> {
+		None
+	}
+
+	/// the items of this page
+	fn items(&self) -> &[Self::Item];
+
+	fn into_items(self) -> Vec<Self::Item>;
+
+	/// number of results of this page and estimated number of all pages
+	fn page_info(&self) -> &PageInfo;
+}
+
+/// implements [`Page`], iteration and accessors of the items for the response
+/// `$response` with items of the type `$item`, which needs the fields
+/// `next_page_token`, `prev_page_token`, `page_info` and `items`, or no
+/// `prev_page_token` with `without_prev_page`
+macro_rules! page_methods {
+	($response:ident => $item:ty) => {
+		$crate::pagination::page_methods!(@impl $response => $item {
+			fn prev_page_token(&self) -> Option<&str> {
+				self.prev_page_token.as_deref()
+			}
+		});
+	};
+	($response:ident => $item:ty, without_prev_page) => {
+		$crate::pagination::page_methods!(@impl $response => $item {});
+	};
+	(@impl $response:ident => $item:ty { $($prev_page_token:item)* }) => {
+		impl $response {
+			/// number of items on this page, unlike the estimated
+			/// `page_info.total_results` of all pages
+			#[must_use]
+			pub fn len(&self) -> usize {
+				self.items.len()
+			}
+
+			/// whether this page contains no items, which can happen for a
+			/// successful request
+			#[must_use]
+			pub fn is_empty(&self) -> bool {
+				self.items.is_empty()
+			}
+
+			/// the first item of this page
+			#[must_use]
+			pub fn first(&self) -> Option<&$item> {
+				self.items.first()
+			}
+
+			/// iterate over the items of this page, iterating over the
+			/// response itself yields the items as well
+			pub fn iter(&self) -> std::slice::Iter<'_, $item> {
+				self.items.iter()
+			}
+		}
+
+		impl $crate::pagination::Page for $response {
+			type Item = $item;
+
+			fn next_page_token(&self) -> Option<&str> {
+				self.next_page_token.as_deref()
+			}
+
+			$($prev_page_token)*
+
+			fn items(&self) -> &[$item] {
+				&self.items
+			}
+
+			fn into_items(self) -> Vec<$item> {
+				self.items
+			}
+
+			fn page_info(&self) -> &$crate::PageInfo {
+				&self.page_info
+			}
+		}
+
+		impl IntoIterator for $response {
+			type Item = $item;
+			type IntoIter = std::vec::IntoIter<$item>;
+
+			fn into_iter(self) -> Self::IntoIter {
+				self.items.into_iter()
+			}
+		}
+
+		impl<'a> IntoIterator for &'a $response {
+			type Item = &'a $item;
+			type IntoIter = std::slice::Iter<'a, $item>;
+
+			fn into_iter(self) -> Self::IntoIter {
+				self.items.iter()
+			}
+		}
+	};
+}
+
+pub(crate) use page_methods;
+
+/// request of an endpoint whose results are split into pages
+pub trait Paginated:
+	IntoFuture<
+		Output = Result<<Self as Paginated>::Response, Error>,
+		IntoFuture = ResponseFuture<<Self as Paginated>::Response>,
+	> + Unpin
+	+ Sized
+{
+	type Response: Page;
+
+	/// unsent copy of this request asking for the page with the given token,
+	/// or for the same page as this request if `page_token` is `None`
+	#[must_use]
+	fn with_page_token(&self, page_token: Option<&str>) -> Self;
+
+	/// unsent copy of this request asking for the page following `page`, a
+	/// response to this request, or `None` if `page` is the last page
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{pagination::Paginated, ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let search = client.search().q("rust lang").max_results(5);
+	///
+	/// let mut page = search.clone().await.unwrap();
+	/// while let Some(next) = search.next_page(&page) {
+	///     page = next.await.unwrap();
+	/// }
+	/// # });
+	/// ```
+	#[must_use]
+	fn next_page(&self, page: &Self::Response) -> Option<Self> {
+		page.next_page_token()
+			.map(|page_token| self.with_page_token(Some(page_token)))
+	}
+
+	/// unsent copy of this request asking for the page preceding `page`, a
+	/// response to this request, or `None` if `page` is the first page, e.g.
+	/// for the buttons of a paged list
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{pagination::Paginated, ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let search = client.search().q("rust lang").max_results(5);
+	///
+	/// let first = search.clone().await.unwrap();
+	/// let second = search.next_page(&first).unwrap().await.unwrap();
+	/// // back to the first page
+	/// let first = search.prev_page(&second).unwrap().await.unwrap();
+	/// # });
+	/// ```
+	#[must_use]
+	fn prev_page(&self, page: &Self::Response) -> Option<Self> {
+		page.prev_page_token()
+			.map(|page_token| self.with_page_token(Some(page_token)))
+	}
+
+	/// stream of all pages, starting with the page this request asks for
+	fn pages(self) -> Paginator<Self> {
+		Paginator::new(self)
+	}
+}
+
+/// [`Stream`](https://docs.rs/futures/0.3/futures/stream/trait.Stream.html) of
+/// pages, requesting the next page once the previous one was received
+///
+/// The stream ends after the last page or after the first error.
+pub struct Paginator<R: Paginated> {
+	template: R,
+	current: Option<ResponseFuture<R::Response>>,
+	remaining_pages: Option<usize>,
+}
+
+impl<R: Paginated> Paginator<R> {
+	#[must_use]
+	pub fn new(request: R) -> Self {
+		Self {
+			current: Some(request.with_page_token(None).into_future()),
+			template: request,
+			remaining_pages: None,
+		}
+	}
+
+	/// fetch at most `pages` pages
+	#[must_use]
+	pub fn take_pages(mut self, pages: usize) -> Self {
+		self.remaining_pages = Some(pages);
+		self
+	}
+
+	/// stream of the items of all pages
+	pub fn items(self) -> impl Stream<Item = Result<<R::Response as Page>::Item, Error>> {
+		self.map_ok(|page| stream::iter(page.into_items().into_iter().map(Ok)))
+			.try_flatten()
+	}
+}
+
+impl<R: Paginated> Stream for Paginator<R> {
+	type Item = Result<R::Response, Error>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if self.remaining_pages == Some(0) {
+			return Poll::Ready(None);
+		}
+		let result = match self.current.as_mut() {
+			Some(request) => futures::ready!(request.poll_unpin(cx)),
+			None => return Poll::Ready(None),
+		};
+		self.current = match &result {
+			Ok(page) => self.template.next_page(page).map(IntoFuture::into_future),
+			Err(_) => None,
+		};
+		self.remaining_pages = self.remaining_pages.map(|pages| pages - 1);
+		Poll::Ready(Some(result))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::Paginated;
+	use crate::{testing::MockTransport, ApiKey, Client, Request};
+
+	#[test]
+	fn next_page_asks_for_the_next_page_token() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"/search?",
+			200,
+			r#"{ "nextPageToken": "CAUQAA", "items": [] }"#,
+		);
+		let client = Client::with_transport(ApiKey::new("key"), mock);
+		let search = client.search().q("rust lang").max_results(5);
+
+		let page = futures::executor::block_on(search.clone().send()).unwrap();
+		let next = search.next_page(&page).unwrap();
+		assert!(next
+			.build_url()
+			.unwrap()
+			.contains("&pageToken=CAUQAA&q=rust+lang"));
+	}
+
+	#[test]
+	fn prev_page_asks_for_the_prev_page_token() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"/search?",
+			200,
+			r#"{ "nextPageToken": "CAUQAA", "items": [] }"#,
+		)
+		.expect(
+			"pageToken=CAUQAA",
+			200,
+			r#"{ "prevPageToken": "CAUQAQ", "nextPageToken": "CAoQAA", "items": [] }"#,
+		);
+		let client = Client::with_transport(ApiKey::new("key"), mock);
+		let search = client.search().q("rust lang").max_results(5);
+
+		futures::executor::block_on(async {
+			let first = search.clone().await.unwrap();
+			assert!(search.prev_page(&first).is_none());
+
+			let second = search.next_page(&first).unwrap().await.unwrap();
+			let back = search.prev_page(&second).unwrap();
+			assert!(back
+				.build_url()
+				.unwrap()
+				.contains("&pageToken=CAUQAQ&q=rust+lang"));
+			let forward = search.next_page(&second).unwrap();
+			assert!(forward
+				.build_url()
+				.unwrap()
+				.contains("&pageToken=CAoQAA&q=rust+lang"));
+		});
+	}
+}
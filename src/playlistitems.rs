@@ -1,53 +1,40 @@
-use std::{
-	future::Future,
-	pin::Pin,
-	task::{Context, Poll},
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::{PageInfo, Thumbnail, Thumbnails};
+#[cfg(feature = "chrono")]
+use super::published::Published;
+#[allow(deprecated)]
+pub use super::search::Order;
+pub use super::search::{
+	ChannelType, EventType, ItemType, SafeSearch, SearchOrder, VideoCaption, VideoDefinition,
+	VideoDimension, VideoDuration, VideoLicense, VideoLocation, VideoType,
+};
+pub use super::Error;
+use super::{
+	error::Serialization,
+	limits,
+	pagination::{self, Paginated},
+	request::{self, Request},
+	AccessToken, ApiKey, ChannelId, Client, PlaylistId, Timestamp, VideoId,
 };
-
-use chrono::{DateTime, Utc};
-use futures::future::BoxFuture;
-use log::debug;
-use serde::{Deserialize, Serialize, Serializer};
-use snafu::{ResultExt, Snafu};
-
-use super::ApiKey;
-
-/// custom error type for the search endpoint
-#[derive(Debug, Snafu)]
-pub enum Error {
-	#[snafu(display("failed to connect to the api: {}", string))]
-	Connection { string: String },
-	#[snafu(display("failed to deserialize: {} {}", string, source))]
-	Deserialization {
-		string: String,
-		source: serde_json::Error,
-	},
-	#[snafu(display("failed to serialize: {}", source))]
-	Serialization {
-		source: serde_urlencoded::ser::Error,
-	},
-}
-
-impl From<surf::Error> for Error {
-	fn from(surf_error: surf::Error) -> Self {
-		Error::Connection {
-			string: surf_error.to_string(),
-		}
-	}
-}
 
 /// request struct for the search endpoint
+#[derive(Debug, Clone)]
 pub struct PlaylistItems {
-	future: Option<BoxFuture<'static, Result<Response, Error>>>,
-	data: Option<PlaylistItemsData>,
+	data: PlaylistItemsData,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct PlaylistItemsData {
+	#[serde(skip)]
+	client: Client,
 	key: ApiKey,
 	part: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	id: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	max_results: Option<u8>,
@@ -56,219 +43,140 @@ struct PlaylistItemsData {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	page_token: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	playlist_id: Option<String>,
+	playlist_id: Option<PlaylistId>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	video_id: Option<String>,
+	video_id: Option<VideoId>,
 }
 
 impl PlaylistItems {
-	const URL: &'static str = "https://www.googleapis.com/youtube/v3/playlistItems";
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "playlistItems";
 
 	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
 	#[must_use]
 	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).playlist_items()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
 		Self {
-			future: None,
-			data: Some(PlaylistItemsData {
-				key,
+			data: PlaylistItemsData {
+				key: client.key.clone(),
+				client,
 				part: String::from("snippet"),
+				fields: None,
 				id: None,
 				max_results: None,
 				on_behalf_of_content_owner: None,
 				page_token: None,
 				playlist_id: None,
 				video_id: None,
-			}),
+			},
 		}
 	}
 
 	#[must_use]
 	pub fn id(mut self, id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.id = Some(id.into());
-		self.data = Some(data);
+		self.data.id = Some(id.into());
 		self
 	}
 
 	#[must_use]
 	pub fn max_results(mut self, max_results: impl Into<u8>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.max_results = Some(max_results.into());
-		self.data = Some(data);
+		self.data.max_results = Some(max_results.into());
 		self
 	}
 
+	/// act on behalf of the content owner with the given id, only usable by
+	/// youtube content partners authorized with an
+	/// [`access_token`](#method.access_token) of a cms account linked to it
 	#[must_use]
 	pub fn on_behalf_of_content_owner(
 		mut self,
 		on_behalf_of_content_owner: impl Into<String>,
 	) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
-		self.data = Some(data);
+		self.data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
 		self
 	}
 
 	#[must_use]
 	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.page_token = Some(page_token.into());
-		self.data = Some(data);
+		self.data.page_token = Some(page_token.into());
 		self
 	}
 
 	#[must_use]
-	pub fn playlist_id(mut self, playlist_id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.playlist_id = Some(playlist_id.into());
-		self.data = Some(data);
+	pub fn playlist_id(mut self, playlist_id: impl Into<PlaylistId>) -> Self {
+		self.data.playlist_id = Some(playlist_id.into());
 		self
 	}
 
 	#[must_use]
-	pub fn video_id(mut self, video_id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_id = Some(video_id.into());
-		self.data = Some(data);
+	pub fn video_id(mut self, video_id: impl Into<VideoId>) -> Self {
+		self.data.video_id = Some(video_id.into());
 		self
 	}
-}
-
-impl Future for PlaylistItems {
-	type Output = Result<Response, Error>;
-
-	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-		if self.future.is_none() {
-			let data = self.data.take().unwrap();
-			self.future = Some(Box::pin(async move {
-				let url = format!(
-					"{}?{}",
-					Self::URL,
-					serde_urlencoded::to_string(&data).context(Serialization)?
-				);
-				debug!("getting {}", url);
-				let response = surf::get(&url).recv_string().await?;
-				serde_json::from_str(&response)
-					.with_context(move || Deserialization { string: response })
-			}));
-		}
 
-		self.future.as_mut().unwrap().as_mut().poll(cx)
+	/// request the `status` part with the privacy status of the videos
+	#[must_use]
+	pub fn status(mut self) -> Self {
+		self.data.part.push_str(",status");
+		self
 	}
-}
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ChannelType {
-	Any,
-	Show,
-}
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum EventType {
-	Completed,
-	Live,
-	Upcoming,
-}
-
-#[derive(Debug, Clone)]
-pub struct VideoLocation {
-	longitude: f32,
-	latitude: f32,
-}
-
-impl VideoLocation {
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
 	#[must_use]
-	pub fn new(longitude: f32, latitude: f32) -> Self {
-		Self {
-			longitude,
-			latitude,
-		}
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
 	}
-}
 
-impl Serialize for VideoLocation {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: Serializer,
-	{
-		serializer.serialize_str(&format!("{},{}", self.longitude, self.latitude))
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
 	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum Order {
-	Date,
-	Rating,
-	Relevance,
-	Title,
-	VideoCount,
-	ViewCount,
-}
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum SafeSearch {
-	Moderate,
-	Strict,
-}
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ItemType {
-	Channel,
-	Playlist,
-	Video,
-}
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoCaption {
-	ClosedCaption,
-	None,
-}
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoDefinition {
-	High,
-	Standard,
+impl Request for PlaylistItems {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		request::exactly_one_filter(&[
+			("id", data.id.is_some()),
+			("playlistId", data.playlist_id.is_some()),
+		])?;
+		limits::check(
+			"maxResults",
+			&limits::MAX_RESULTS,
+			data.max_results.map(u16::from),
+		)?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub enum VideoDimension {
-	#[serde(rename = "3d")]
-	Three,
-	#[serde(rename = "2d")]
-	Two,
-}
+request::request_methods!(PlaylistItems => Response, "youtube#playlistItemListResponse");
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoDuration {
-	Long,
-	Medium,
-	Short,
-}
+impl Paginated for PlaylistItems {
+	type Response = Response;
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoLicense {
-	CreativeCommon,
-	Youtube,
-}
-
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoType {
-	Episode,
-	Movie,
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Response {
 	pub kind: String,
 	pub etag: String,
@@ -278,14 +186,10 @@ pub struct Response {
 	pub items: Vec<PlaylistResult>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PageInfo {
-	pub total_results: i64,
-	pub results_per_page: i64,
-}
+pagination::page_methods!(Response => PlaylistResult);
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct PlaylistResult {
 	pub kind: String,
 	pub etag: String,
@@ -293,58 +197,90 @@ pub struct PlaylistResult {
 	pub snippet: Snippet,
 	pub content_details: Option<ContentDetails>,
 	pub status: Option<Status>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// the video of the item was published at `contentDetails.videoPublishedAt`,
+/// `snippet.publishedAt` is when it was added to the playlist and only used if
+/// the `contentDetails` part was not requested
+#[cfg(feature = "chrono")]
+impl Published for PlaylistResult {
+	fn published_at(&self) -> Option<Timestamp> {
+		self.content_details
+			.as_ref()
+			.and_then(|content_details| content_details.video_published_at)
+			.or(self.snippet.published_at)
+	}
+}
+
+/// snippet of a playlist item, where `channel_id` and `channel_title` belong
+/// to the owner of the playlist and `video_owner_channel_id` and
+/// `video_owner_channel_title` to the owner of the video
+///
+/// ```rust
+/// # use yt_api::playlistitems::Snippet;
+/// let snippet: Snippet = serde_json::from_str(r#"{
+///     "publishedAt": "2021-03-01T12:00:00Z",
+///     "channelId": "UCVSVvuqXYbtrHzfqsDw8ITg",
+///     "title": "Rust in 100 Seconds",
+///     "channelTitle": "curated rust",
+///     "playlistId": "PLOU2XLYxmsIIM9h1Ybw2DuRw6o2fkNMeR",
+///     "position": 3,
+///     "resourceId": { "kind": "youtube#video", "videoId": "5C_HPTJg5ek" },
+///     "videoOwnerChannelTitle": "Fireship",
+///     "videoOwnerChannelId": "UCsBjURrPoezykLs9EqgamOA"
+/// }"#).unwrap();
+///
+/// assert_eq!(snippet.channel_id.as_ref().unwrap(), "UCVSVvuqXYbtrHzfqsDw8ITg");
+/// assert_eq!(snippet.video_owner_channel_id.as_ref().unwrap(), "UCsBjURrPoezykLs9EqgamOA");
+/// assert_eq!(snippet.video_owner_channel_title.as_deref(), Some("Fireship"));
+///
+/// // left out by the api for deleted and private videos
+/// let snippet: Snippet = serde_json::from_str(r#"{ "title": "Deleted video" }"#).unwrap();
+/// assert_eq!(snippet.video_owner_channel_id, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Snippet {
-	pub published_at: Option<DateTime<Utc>>,
-	pub channel_id: Option<String>,
+	pub published_at: Option<Timestamp>,
+	pub channel_id: Option<ChannelId>,
 	pub title: Option<String>,
 	pub description: Option<String>,
 	pub thumbnails: Option<Thumbnails>,
 	pub channel_title: Option<String>,
 	pub video_owner_channel_title: Option<String>,
-	pub video_owner_channel_id: Option<String>,
-	pub playlist_id: Option<String>,
+	pub video_owner_channel_id: Option<ChannelId>,
+	pub playlist_id: Option<PlaylistId>,
 	pub position: Option<u32>,
 	pub resource_id: Resource,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct Thumbnails {
-	pub default: Option<Thumbnail>,
-	pub medium: Option<Thumbnail>,
-	pub high: Option<Thumbnail>,
-	pub standard: Option<Thumbnail>,
-	pub maxres: Option<Thumbnail>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct Thumbnail {
-	pub url: String,
-	pub width: Option<u64>,
-	pub height: Option<u64>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Resource {
 	pub kind: String,
-	pub video_id: String,
+	pub video_id: VideoId,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct ContentDetails {
-	pub video_id: String,
-	pub start_at: String,
-	pub end_at: String,
-	pub note: String,
-	pub video_published_at: Option<DateTime<Utc>>,
+	pub video_id: VideoId,
+	pub start_at: Option<String>,
+	pub end_at: Option<String>,
+	pub note: Option<String>,
+	pub video_published_at: Option<Timestamp>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Status {
 	pub privacy_status: String,
 }
@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::{PageInfo, Thumbnail, Thumbnails};
+pub use super::Error;
+use super::{
+	error::{MissingAccessToken, Serialization},
+	limits,
+	pagination::{self, Paginated},
+	request::{self, Request},
+	AccessToken, ApiKey, ChannelId, Client, PlaylistId, Timestamp,
+};
+
+/// request struct for the playlists endpoint
+///
+/// Exactly one of the filters [`id`](#method.id),
+/// [`channel_id`](#method.channel_id) and [`mine`](#method.mine) has to be
+/// set.
+///
+/// ```rust
+/// # use yt_api::{ApiKey, Client, Error, Request};
+/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+/// let playlists = || client.playlists();
+///
+/// let invalid = vec![
+///     (playlists().id("PLOU2XLYxmsIIM9h1Ybw2DuRw6o2fkNMeR").channel_id("UC_x5XG1OV2P6uZZ5FSM9Ttw"), "id cannot be combined with channelId"),
+///     (playlists().id("PLOU2XLYxmsIIM9h1Ybw2DuRw6o2fkNMeR").mine(), "id cannot be combined with mine"),
+///     (playlists().channel_id("UC_x5XG1OV2P6uZZ5FSM9Ttw").mine(), "channelId cannot be combined with mine"),
+/// ];
+/// for (playlists, reason) in invalid {
+///     match playlists.build_url() {
+///         Err(Error::InvalidRequest { reason: actual }) => assert_eq!(actual, reason),
+///         result => panic!("{} was not reported: {:?}", reason, result),
+///     }
+/// }
+///
+/// assert!(playlists().channel_id("UC_x5XG1OV2P6uZZ5FSM9Ttw").build_url().is_ok());
+/// assert!(matches!(
+///     playlists().build_url(),
+///     Err(Error::InvalidParameter { field, .. }) if field == "filter"
+/// ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Playlists {
+	data: PlaylistsData,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlaylistsData {
+	#[serde(skip)]
+	client: Client,
+	key: ApiKey,
+	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	id: Option<PlaylistId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	channel_id: Option<ChannelId>,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	mine: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_results: Option<u8>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	on_behalf_of_content_owner: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	page_token: Option<String>,
+}
+
+impl Playlists {
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "playlists";
+
+	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	#[must_use]
+	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).playlists()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
+		Self {
+			data: PlaylistsData {
+				key: client.key.clone(),
+				client,
+				part: String::from("snippet,contentDetails,status"),
+				fields: None,
+				id: None,
+				channel_id: None,
+				mine: false,
+				max_results: None,
+				on_behalf_of_content_owner: None,
+				page_token: None,
+			},
+		}
+	}
+
+	/// the playlist to look up
+	#[must_use]
+	pub fn id(mut self, id: impl Into<PlaylistId>) -> Self {
+		self.data.id = Some(id.into());
+		self
+	}
+
+	/// list the public playlists of the channel with the given id
+	#[must_use]
+	pub fn channel_id(mut self, channel_id: impl Into<ChannelId>) -> Self {
+		self.data.channel_id = Some(channel_id.into());
+		self
+	}
+
+	/// list the playlists of the authorized user, including the private ones,
+	/// requires an [`access_token`](#method.access_token)
+	#[must_use]
+	pub fn mine(mut self) -> Self {
+		self.data.mine = true;
+		self
+	}
+
+	#[must_use]
+	pub fn max_results(mut self, max_results: impl Into<u8>) -> Self {
+		self.data.max_results = Some(max_results.into());
+		self
+	}
+
+	/// act on behalf of the content owner with the given id, only usable by
+	/// youtube content partners authorized with an
+	/// [`access_token`](#method.access_token) of a cms account linked to it
+	#[must_use]
+	pub fn on_behalf_of_content_owner(
+		mut self,
+		on_behalf_of_content_owner: impl Into<String>,
+	) -> Self {
+		self.data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
+		self
+	}
+
+	#[must_use]
+	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
+		self.data.page_token = Some(page_token.into());
+		self
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+}
+
+impl PlaylistsData {
+	const OWNER_FILTERS: &'static [&'static str] = &["mine"];
+
+	fn check_filters(&self) -> Result<(), Error> {
+		let filters = [
+			("id", self.id.is_some()),
+			("channelId", self.channel_id.is_some()),
+			("mine", self.mine),
+		];
+		match request::exactly_one_filter(&filters)? {
+			filter
+				if Self::OWNER_FILTERS.contains(&filter) && self.client.access_token.is_none() =>
+			{
+				MissingAccessToken {
+					required_by: format!("the {} filter", filter),
+				}
+				.fail()
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+impl Request for Playlists {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.check_filters()?;
+		limits::check(
+			"maxResults",
+			&limits::MAX_RESULTS,
+			data.max_results.map(u16::from),
+		)?;
+		Ok(data.client.url(
+			Self::PATH,
+			&serde_urlencoded::to_string(data).context(Serialization)?,
+		))
+	}
+}
+
+request::request_methods!(Playlists => Response, "youtube#playlistListResponse");
+
+impl Paginated for Playlists {
+	type Response = Response;
+
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Response {
+	pub kind: String,
+	pub etag: String,
+	pub next_page_token: Option<String>,
+	pub prev_page_token: Option<String>,
+	pub page_info: PageInfo,
+	pub items: Vec<Playlist>,
+}
+
+pagination::page_methods!(Response => Playlist);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Playlist {
+	pub kind: String,
+	pub etag: String,
+	pub id: PlaylistId,
+	pub snippet: Snippet,
+	pub content_details: Option<ContentDetails>,
+	pub status: Option<Status>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snippet {
+	pub published_at: Option<Timestamp>,
+	pub channel_id: Option<ChannelId>,
+	pub title: Option<String>,
+	pub description: Option<String>,
+	pub thumbnails: Option<Thumbnails>,
+	pub channel_title: Option<String>,
+	pub default_language: Option<String>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ContentDetails {
+	pub item_count: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Status {
+	pub privacy_status: String,
+}
@@ -0,0 +1,189 @@
+//! proxy the requests of a client are sent through
+
+use std::fmt::{self, Debug};
+
+use super::Error;
+
+/// http or socks proxy for the requests of a
+/// [`Client`](struct.Client.html), see
+/// [`Client::proxy`](struct.Client.html#method.proxy)
+///
+/// ```rust
+/// # use yt_api::Proxy;
+/// let proxy = Proxy::new("http://proxy.internal:3128")
+///     .unwrap()
+///     .basic_auth("user", "secret")
+///     .no_proxy(vec!["localhost", ".internal"]);
+/// assert_eq!(proxy.url(), "http://proxy.internal:3128");
+/// assert!(!format!("{:?}", proxy).contains("secret"));
+///
+/// assert!(Proxy::new("socks5h://127.0.0.1:1080").is_ok());
+/// assert!(Proxy::new("proxy.internal:3128").is_err());
+/// assert!(Proxy::new("ftp://proxy.internal").is_err());
+/// assert!(Proxy::new("http://:3128").is_err());
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct Proxy {
+	url: String,
+	credentials: Option<(String, String)>,
+	no_proxy: Vec<String>,
+}
+
+impl Proxy {
+	/// schemes of the supported proxies, socks proxies need the `socks`
+	/// feature with reqwest
+	const SCHEMES: [&'static str; 6] = ["http", "https", "socks4", "socks4a", "socks5", "socks5h"];
+
+	/// proxy at `url`, like `http://proxy.internal:3128` or
+	/// `socks5h://127.0.0.1:1080`, credentials may be part of the url
+	pub fn new(url: impl Into<String>) -> Result<Self, Error> {
+		let proxy = Self {
+			url: url.into(),
+			credentials: None,
+			no_proxy: Vec::new(),
+		};
+		proxy.validate()?;
+		Ok(proxy)
+	}
+
+	/// authenticate with the proxy using basic authentication
+	#[must_use]
+	pub fn basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+		self.credentials = Some((username.into(), password.into()));
+		self
+	}
+
+	/// connect to `hosts` directly, a leading dot matches all subdomains
+	#[must_use]
+	pub fn no_proxy<I>(mut self, hosts: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<String>,
+	{
+		self.no_proxy.extend(
+			hosts
+				.into_iter()
+				.map(Into::into)
+				.map(|host| host.trim().to_string())
+				.filter(|host| !host.is_empty()),
+		);
+		self
+	}
+
+	/// the proxy of the `HTTPS_PROXY` or `ALL_PROXY` environment variable,
+	/// bypassed for the hosts of `NO_PROXY`
+	///
+	/// The lower case variants are read as well. An invalid proxy is logged
+	/// and ignored.
+	///
+	/// ```rust
+	/// # use yt_api::Proxy;
+	/// std::env::set_var("HTTPS_PROXY", "http://proxy.internal:3128");
+	/// std::env::set_var("NO_PROXY", "localhost, .internal");
+	///
+	/// let proxy = Proxy::from_env().unwrap();
+	/// assert_eq!(proxy, Proxy::new("http://proxy.internal:3128").unwrap().no_proxy(vec!["localhost", ".internal"]));
+	///
+	/// std::env::remove_var("HTTPS_PROXY");
+	/// assert_eq!(Proxy::from_env(), None);
+	/// ```
+	#[must_use]
+	pub fn from_env() -> Option<Self> {
+		let var = |names: &[&str]| {
+			names
+				.iter()
+				.filter_map(|name| std::env::var(name).ok())
+				.find(|value| !value.trim().is_empty())
+		};
+		let url = var(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"])?;
+		let proxy = match Self::new(url.trim()) {
+			Ok(proxy) => proxy,
+			Err(error) => {
+				log::warn!("ignoring the proxy of the environment: {}", error);
+				return None;
+			}
+		};
+		let no_proxy = var(&["NO_PROXY", "no_proxy"]).unwrap_or_default();
+		Some(proxy.no_proxy(no_proxy.split(',')))
+	}
+
+	/// url of the proxy
+	#[must_use]
+	pub fn url(&self) -> &str {
+		&self.url
+	}
+
+	#[cfg(any(
+		feature = "curl-client",
+		all(feature = "reqwest", not(target_arch = "wasm32"))
+	))]
+	pub(crate) fn credentials(&self) -> Option<(&str, &str)> {
+		self.credentials
+			.as_ref()
+			.map(|(username, password)| (username.as_str(), password.as_str()))
+	}
+
+	#[cfg(any(
+		feature = "curl-client",
+		all(feature = "reqwest", not(target_arch = "wasm32"))
+	))]
+	pub(crate) fn no_proxy_hosts(&self) -> &[String] {
+		&self.no_proxy
+	}
+
+	/// the url without the credentials it might contain
+	fn redacted_url(&self) -> String {
+		match self.url.split_once("://") {
+			Some((scheme, rest)) => match rest.split('/').next().unwrap_or_default().rfind('@') {
+				Some(at) => format!("{}://***@{}", scheme, &rest[at + 1..]),
+				None => self.url.clone(),
+			},
+			None => self.url.clone(),
+		}
+	}
+
+	/// fails with [`Error::InvalidParameter`] unless the url has a supported
+	/// scheme and a host
+	fn validate(&self) -> Result<(), Error> {
+		let invalid = |message: &str| Error::InvalidParameter {
+			field: String::from("proxy"),
+			message: format!("{} {}", self.redacted_url(), message),
+		};
+		let (scheme, rest) = self
+			.url
+			.split_once("://")
+			.ok_or_else(|| invalid("has no scheme"))?;
+		if !Self::SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) {
+			return Err(invalid("is neither an http nor a socks proxy"));
+		}
+		let authority = rest.split('/').next().unwrap_or_default();
+		let host = authority.rsplit('@').next().unwrap_or_default();
+		let (host, port) = match host.rfind(':') {
+			Some(colon) if !host.ends_with(']') => (&host[..colon], Some(&host[colon + 1..])),
+			_ => (host, None),
+		};
+		if host.is_empty() {
+			return Err(invalid("has no host"));
+		}
+		if port.is_some_and(|port| port.parse::<u16>().is_err()) {
+			return Err(invalid("has an invalid port"));
+		}
+		Ok(())
+	}
+}
+
+impl Debug for Proxy {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Proxy")
+			.field("url", &self.redacted_url())
+			.field(
+				"credentials",
+				&self
+					.credentials
+					.as_ref()
+					.map(|(username, _)| (username, "***")),
+			)
+			.field("no_proxy", &self.no_proxy)
+			.finish()
+	}
+}
@@ -0,0 +1,62 @@
+//! publish time of results of different endpoints
+
+/// timestamp of a response, parsed with the `chrono` feature
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// timestamp of a response, the rfc 3339 string sent by the api since the
+/// `chrono` feature is disabled
+///
+/// ```rust
+/// # #[cfg(not(feature = "chrono"))] {
+/// # use yt_api::videos::Snippet;
+/// let snippet: Snippet = serde_json::from_str(r#"{ "publishedAt": "2021-01-01T00:00:00.000Z" }"#).unwrap();
+/// assert_eq!(snippet.published_at.as_deref(), Some("2021-01-01T00:00:00.000Z"));
+/// # }
+/// ```
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+/// result which refers to a published video, playlist or channel, only
+/// available with the `chrono` feature
+///
+/// Generic code can use it to merge results of several endpoints into one
+/// timeline:
+///
+/// ```rust
+/// # #[cfg(feature = "chrono")] {
+/// # use yt_api::{playlistitems::PlaylistResult, search::SearchResult, videos::VideoResult, Published};
+/// let video: VideoResult = serde_json::from_str(r#"{
+///     "snippet": { "publishedAt": "2021-03-01T00:00:00Z" }
+/// }"#).unwrap();
+/// // the video was published before it was added to the playlist
+/// let playlist_item: PlaylistResult = serde_json::from_str(r#"{
+///     "snippet": { "publishedAt": "2021-05-01T00:00:00Z" },
+///     "contentDetails": { "videoPublishedAt": "2021-01-01T00:00:00Z" }
+/// }"#).unwrap();
+/// let search_result: SearchResult = serde_json::from_str(r#"{
+///     "snippet": { "publishedAt": "2021-02-01T00:00:00.5Z" }
+/// }"#).unwrap();
+/// let unknown: VideoResult = serde_json::from_str("{}").unwrap();
+///
+/// let mut timeline: Vec<&dyn Published> = vec![&video, &unknown, &search_result, &playlist_item];
+/// timeline.sort_by_key(|result| result.published_at());
+///
+/// let dates = timeline
+///     .iter()
+///     .map(|result| result.published_at().map(|date| date.to_rfc3339()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(dates, [
+///     None,
+///     Some(String::from("2021-01-01T00:00:00+00:00")),
+///     Some(String::from("2021-02-01T00:00:00.500+00:00")),
+///     Some(String::from("2021-03-01T00:00:00+00:00")),
+/// ]);
+/// # }
+/// ```
+#[cfg(feature = "chrono")]
+pub trait Published {
+	/// when the video, playlist or channel was published, `None` if the
+	/// field was not requested
+	fn published_at(&self) -> Option<Timestamp>;
+}
@@ -0,0 +1,44 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use std::{sync::Mutex, time::Duration};
+
+use futures_timer::Delay;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+/// token bucket shared by the requests of a client
+///
+/// Implemented as a generic cell rate algorithm: every request reserves the
+/// next free slot when it asks for permission, so waiting requests are let
+/// through in the order they arrived and none of them can starve.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+	interval: Duration,
+	tolerance: Duration,
+	theoretical_arrival: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+	pub(crate) fn new(requests_per_second: f64, burst: u32) -> Self {
+		let interval = Duration::from_secs_f64(1.0 / requests_per_second);
+		Self {
+			interval,
+			tolerance: interval * burst.saturating_sub(1),
+			theoretical_arrival: Mutex::new(None),
+		}
+	}
+
+	/// waits until the request may be sent
+	pub(crate) async fn acquire(&self) {
+		let now = Instant::now();
+		let send_at = {
+			let mut theoretical_arrival = self.theoretical_arrival.lock().unwrap();
+			let arrival = theoretical_arrival.map_or(now, |arrival| arrival.max(now));
+			*theoretical_arrival = Some(arrival + self.interval);
+			arrival.checked_sub(self.tolerance).unwrap_or(now)
+		};
+		if send_at > now {
+			Delay::new(send_at - now).await;
+		}
+	}
+}
@@ -0,0 +1,555 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use std::{
+	fmt,
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+use futures::{
+	future::{select, Either},
+	ready, FutureExt,
+};
+use futures_timer::Delay;
+use log::debug;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use super::{
+	error::{Cancelled, InvalidParameter, InvalidRequest, Timeout, UnexpectedKind},
+	json_path,
+	observer::redact,
+	transport::{HttpRequest, Method},
+	Client, Error,
+};
+
+/// sends a request to the api and deserializes the successful response,
+/// retrying transient failures according to the retry policy of the client
+/// and charging the client with `cost` quota units per attempt
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn send<T: DeserializeOwned>(
+	client: &Client,
+	method: Method,
+	url: &str,
+	cost: u32,
+) -> Result<T, Error> {
+	send_attempts(client, method, url, cost).await
+}
+
+/// like [`send`] without the `tracing` feature, running the attempts in a
+/// span of the request
+#[cfg(feature = "tracing")]
+pub(crate) async fn send<T: DeserializeOwned>(
+	client: &Client,
+	method: Method,
+	url: &str,
+	cost: u32,
+) -> Result<T, Error> {
+	use tracing::Instrument;
+
+	send_attempts(client, method, url, cost)
+		.instrument(request_span(method, url))
+		.await
+}
+
+/// span of a request, with the credentials redacted from all fields
+#[cfg(feature = "tracing")]
+fn request_span(method: Method, url: &str) -> tracing::Span {
+	let url_redacted = redact(url);
+	let (path, query) = url_redacted
+		.split_once('?')
+		.unwrap_or((url_redacted.as_str(), ""));
+	let endpoint = path.rsplit('/').next().unwrap_or_default();
+	let page_token = query
+		.split('&')
+		.find_map(|parameter| parameter.strip_prefix("pageToken="));
+	tracing::info_span!(
+		"yt_api.request",
+		endpoint,
+		method = method.as_str(),
+		parameters = query,
+		page_token,
+	)
+}
+
+async fn send_attempts<T: DeserializeOwned>(
+	client: &Client,
+	method: Method,
+	url: &str,
+	cost: u32,
+) -> Result<T, Error> {
+	client.check_headers()?;
+	let retry = client
+		.retry
+		.as_ref()
+		.filter(|retry| retry.allows(method.is_idempotent()));
+	let mut attempt = 1;
+	loop {
+		if let Some(token) = &client.cancellation {
+			if token.is_cancelled() {
+				return Cancelled { url: redact(url) }.fail();
+			}
+		}
+		client.charge_quota(cost)?;
+		if let Some(rate_limiter) = &client.rate_limiter {
+			rate_limiter.acquire().await;
+		}
+		let result = send_once(client, method, url).await;
+		match (result, retry) {
+			(Err(error), Some(retry)) if retry.should_retry(attempt, &error) => {
+				let delay = retry.delay(attempt);
+				#[cfg(feature = "tracing")]
+				tracing::warn!(attempt, error = %error, delay = ?delay, "retrying the request");
+				debug!(
+					"attempt {} failed: {}, retrying in {:?}",
+					attempt, error, delay
+				);
+				match &client.cancellation {
+					// the next attempt fails if the token was cancelled
+					Some(token) => {
+						select(Delay::new(delay), Box::pin(token.cancelled())).await;
+					}
+					None => Delay::new(delay).await,
+				}
+				attempt += 1;
+			}
+			(Err(error), Some(_)) if attempt > 1 => {
+				return Err(Error::Retry {
+					attempts: attempt,
+					source: Box::new(error),
+				})
+			}
+			(result, _) => return result,
+		}
+	}
+}
+
+async fn send_once<T: DeserializeOwned>(
+	client: &Client,
+	method: Method,
+	url: &str,
+) -> Result<T, Error> {
+	let url_redacted = redact(url);
+	client.observer.on_request(&url_redacted);
+	#[cfg(feature = "tracing")]
+	tracing::debug!("sending the request");
+	let mut headers = vec![(String::from("User-Agent"), client.user_agent.clone())];
+	if let Some(access_token) = &client.access_token {
+		headers.push((String::from("Authorization"), access_token.bearer()));
+	}
+	headers.extend(client.headers.iter().cloned());
+	let execute = client.transport.execute(HttpRequest {
+		method,
+		url: url.to_string(),
+		headers,
+		body: None,
+	});
+	let start = Instant::now();
+	let response = match client.timeout {
+		Some(duration) => match select(execute, Delay::new(duration)).await {
+			Either::Left((response, _)) => response,
+			Either::Right(_) => {
+				return Timeout {
+					url: url_redacted,
+					duration,
+				}
+				.fail()
+			}
+		},
+		None => execute.await,
+	}
+	.map_err(|transport_error| Error::connection(&url_redacted, &transport_error))?;
+	client
+		.observer
+		.on_response(response.status, start.elapsed());
+	#[cfg(feature = "tracing")]
+	tracing::debug!(
+		status = response.status,
+		bytes = response.body.len(),
+		elapsed = ?start.elapsed(),
+		"received the response"
+	);
+	if !(200..300).contains(&response.status) {
+		return Err(Error::from_response(
+			&url_redacted,
+			response.status,
+			response.body,
+		));
+	}
+	deserialize(url_redacted, response.body)
+}
+
+/// like [`send`], but also returns the json of the response, which is
+/// parsed only once and then converted to `T`
+pub(crate) async fn send_raw<T: DeserializeOwned>(
+	client: &Client,
+	method: Method,
+	url: &str,
+	cost: u32,
+) -> Result<(T, Value), Error> {
+	let value = send::<Value>(client, method, url, cost).await?;
+	match T::deserialize(&value) {
+		Ok(response) => Ok((response, value)),
+		// errors of a conversion from a value have no position, parse the body
+		// again only to find the path of the failed field
+		Err(_) => deserialize(redact(url), value.to_string()).map(|response| (response, value)),
+	}
+}
+
+fn deserialize<T: DeserializeOwned>(url_redacted: String, body: String) -> Result<T, Error> {
+	#[cfg(feature = "tracing")]
+	let start = Instant::now();
+	let result = serde_json::from_str(&body);
+	#[cfg(feature = "tracing")]
+	tracing::debug!(
+		elapsed = ?start.elapsed(),
+		success = result.is_ok(),
+		"deserialized the response"
+	);
+	result.map_err(|source| Error::Deserialization {
+		url: url_redacted,
+		path: json_path::path_at(&body, source.line(), source.column()),
+		string: Error::body_excerpt(&body),
+		source,
+	})
+}
+
+/// fails with [`Error::UnexpectedKind`] unless `kind` is the `expected` kind
+/// of the response of an endpoint, or empty because `fields` left it out
+pub(crate) fn check_kind(expected: &'static str, kind: &str) -> Result<(), Error> {
+	if kind.is_empty() || kind == expected {
+		return Ok(());
+	}
+	UnexpectedKind {
+		expected,
+		got: kind,
+	}
+	.fail()
+}
+
+/// implements the methods shared by all endpoints for the request struct
+/// `$request`, which needs a `data.client`, a `QUOTA_COST` and an
+/// implementation of [`Request`] building its url, and whose responses are
+/// of the kind `$kind`
+macro_rules! request_methods {
+	($request:ident => $response:ty, $kind:literal) => {
+		impl $request {
+			/// quota units charged by the api for each call of this endpoint
+			#[must_use]
+			pub fn quota_cost(&self) -> u32 {
+				Self::QUOTA_COST
+			}
+
+			/// send the request to another api root than the default
+			/// `https://www.googleapis.com/youtube/v3`, e.g. a proxy or a mock server
+			#[must_use]
+			pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+				self.data.client.base_url = base_url.into();
+				self
+			}
+
+			/// retry transient failures according to a [`RetryPolicy`](../struct.RetryPolicy.html)
+			#[must_use]
+			pub fn retry(mut self, retry: $crate::RetryPolicy) -> Self {
+				self.data.client.retry = Some(retry);
+				self
+			}
+
+			/// report this request to `observer` instead of the observer of the client
+			#[must_use]
+			pub fn with_observer(
+				mut self,
+				observer: std::sync::Arc<dyn $crate::observer::RequestObserver>,
+			) -> Self {
+				self.data.client.observer = observer;
+				self
+			}
+
+			/// abort the request if it takes longer than `timeout`, overriding the
+			/// timeout of the client
+			#[must_use]
+			pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+				self.data.client.timeout = Some(timeout);
+				self
+			}
+
+			/// fail the request once `token` is cancelled, overriding the token of
+			/// the client, see [`CancellationToken`](../struct.CancellationToken.html)
+			#[must_use]
+			pub fn with_cancel(mut self, token: $crate::CancellationToken) -> Self {
+				self.data.client.cancellation = Some(token);
+				self
+			}
+
+			/// send the request, awaiting the request itself sends it as well
+			pub fn send(self) -> $crate::ResponseFuture<$response> {
+				let url = $crate::Request::build_url(&self);
+				let client = self.data.client;
+				$crate::ResponseFuture::new(stringify!($request), async move {
+					let response: $response = $crate::request::send(
+						&client,
+						$crate::transport::Method::Get,
+						&url?,
+						Self::QUOTA_COST,
+					)
+					.await?;
+					$crate::request::check_kind($kind, &response.kind)?;
+					Ok(response)
+				})
+			}
+
+			/// send the request and keep the json of the response besides the
+			/// deserialized response, e.g. to read fields this crate does not model
+			///
+			/// The json is returned as it was received, the api key is only
+			/// redacted from the urls passed to observers and errors.
+			pub fn send_raw(self) -> $crate::ResponseFuture<($response, serde_json::Value)> {
+				let url = $crate::Request::build_url(&self);
+				let client = self.data.client;
+				$crate::ResponseFuture::new(stringify!($request), async move {
+					let (response, json): ($response, serde_json::Value) =
+						$crate::request::send_raw(
+							&client,
+							$crate::transport::Method::Get,
+							&url?,
+							Self::QUOTA_COST,
+						)
+						.await?;
+					$crate::request::check_kind($kind, &response.kind)?;
+					Ok((response, json))
+				})
+			}
+		}
+
+		impl std::future::IntoFuture for $request {
+			type Output = Result<$response, $crate::Error>;
+			type IntoFuture = $crate::ResponseFuture<$response>;
+
+			fn into_future(self) -> Self::IntoFuture {
+				self.send()
+			}
+		}
+	};
+}
+
+pub(crate) use request_methods;
+
+/// the filter of `filters` which is set, failing unless exactly one is
+pub(crate) fn exactly_one_filter(filters: &[(&'static str, bool)]) -> Result<&'static str, Error> {
+	let mut set = filters
+		.iter()
+		.filter(|(_, set)| *set)
+		.map(|(filter, _)| *filter);
+	match (set.next(), set.next()) {
+		(Some(filter), None) => Ok(filter),
+		(Some(first), Some(second)) => InvalidRequest {
+			reason: format!("{} cannot be combined with {}", first, second),
+		}
+		.fail(),
+		(None, _) => InvalidParameter {
+			field: "filter",
+			message: format!(
+				"one of {} is required",
+				filters
+					.iter()
+					.map(|(filter, _)| *filter)
+					.collect::<Vec<_>>()
+					.join(", ")
+			),
+		}
+		.fail(),
+	}
+}
+
+/// request of an endpoint, which can be inspected without sending it
+pub trait Request {
+	/// url the request would be sent to, including the api key
+	///
+	/// The parameters are validated and serialized exactly like when the
+	/// request is sent.
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let url = client.videos().id("DnJgoWDxG2A").build_url().unwrap();
+	///
+	/// assert!(url.starts_with("https://www.googleapis.com/youtube/v3/videos?"));
+	/// assert!(url.contains("key=your-youtube-api-key"));
+	/// assert!(url.contains("&id=DnJgoWDxG2A"));
+	/// ```
+	fn build_url(&self) -> Result<String, Error>;
+
+	/// [`build_url`](#tymethod.build_url) with the credentials redacted, e.g.
+	/// for logging
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let url = client.search().q("rust lang").build_url_redacted().unwrap();
+	///
+	/// assert!(url.contains("key=…redacted…"));
+	/// assert!(url.contains("q=rust+lang"));
+	/// ```
+	fn build_url_redacted(&self) -> Result<String, Error> {
+		self.build_url().map(|url| redact(&url))
+	}
+}
+
+/// future of a sent request, resolving to the deserialized response
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ResponseFuture<T> {
+	name: &'static str,
+	state: State<T>,
+}
+
+/// boxed future of a request, which is `Send` except on wasm
+#[cfg(not(target_arch = "wasm32"))]
+type InFlight<T> = futures::future::BoxFuture<'static, Result<T, Error>>;
+
+/// boxed future of a request, which is `Send` except on wasm
+#[cfg(target_arch = "wasm32")]
+type InFlight<T> = futures::future::LocalBoxFuture<'static, Result<T, Error>>;
+
+/// polling state of a [`ResponseFuture`]
+enum State<T> {
+	/// sent, waiting for the response
+	InFlight(InFlight<T>),
+	/// the response was returned
+	Done,
+}
+
+impl<T> ResponseFuture<T> {
+	#[cfg(not(target_arch = "wasm32"))]
+	pub(crate) fn new(
+		name: &'static str,
+		future: impl Future<Output = Result<T, Error>> + Send + 'static,
+	) -> Self {
+		Self {
+			name,
+			state: State::InFlight(Box::pin(future)),
+		}
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	pub(crate) fn new(
+		name: &'static str,
+		future: impl Future<Output = Result<T, Error>> + 'static,
+	) -> Self {
+		Self {
+			name,
+			state: State::InFlight(Box::pin(future)),
+		}
+	}
+}
+
+impl<T> Future for ResponseFuture<T> {
+	type Output = Result<T, Error>;
+
+	/// drives the request to completion
+	///
+	/// # Panics
+	///
+	/// if polled again after the response was returned, like most futures
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		match &mut self.state {
+			State::InFlight(future) => {
+				let result = ready!(future.poll_unpin(cx));
+				self.state = State::Done;
+				Poll::Ready(result)
+			}
+			State::Done => panic!("`{}` polled after completion", self.name),
+		}
+	}
+}
+
+impl<T> fmt::Debug for ResponseFuture<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ResponseFuture")
+			.field("name", &self.name)
+			.field("done", &matches!(self.state, State::Done))
+			.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::Arc, time::Duration};
+
+	use futures::FutureExt;
+	use serde_json::Value;
+
+	use super::send;
+	use crate::{testing::MockTransport, transport::Method, ApiKey, Client, Error, RetryPolicy};
+
+	fn client(mock: &Arc<MockTransport>, retry: RetryPolicy) -> Client {
+		Client::with_transport(ApiKey::new("key"), mock.clone())
+			.retry(retry.base_delay(Duration::ZERO))
+	}
+
+	#[test]
+	fn transient_failures_are_retried() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 503, "")
+			.expect("/videos?", 503, "")
+			.expect("/videos?", 200, r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#);
+		let client = client(&mock, RetryPolicy::exponential(3));
+
+		let response = futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send());
+		assert_eq!(response.unwrap().items[0].id, "DnJgoWDxG2A");
+		mock.assert_done();
+	}
+
+	#[test]
+	fn last_failure_is_returned_with_the_attempts() {
+		let mock = Arc::new(MockTransport::new());
+		for _ in 0..3 {
+			mock.expect("/videos?", 503, "");
+		}
+		let client = client(&mock, RetryPolicy::exponential(3));
+
+		match futures::executor::block_on(client.videos().id("DnJgoWDxG2A").send()) {
+			Err(Error::Retry { attempts, source }) => {
+				assert_eq!(attempts, 3);
+				assert!(matches!(*source, Error::Api { status: 503, .. }));
+			}
+			result => panic!("unexpected {:?}", result),
+		}
+		mock.assert_done();
+	}
+
+	#[test]
+	fn non_idempotent_requests_are_only_retried_if_allowed() {
+		let url = "https://www.googleapis.com/youtube/v3/videos?part=id";
+
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 503, "")
+			.expect("/videos?", 200, "{}");
+		let not_retried = client(&mock, RetryPolicy::exponential(3));
+		let result = futures::executor::block_on(send::<Value>(&not_retried, Method::Post, url, 1));
+		assert!(matches!(result, Err(Error::Api { status: 503, .. })));
+		assert_eq!(mock.requests().len(), 1);
+
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 503, "")
+			.expect("/videos?", 200, "{}");
+		let retried = client(&mock, RetryPolicy::exponential(3).retry_non_idempotent());
+		let result = futures::executor::block_on(send::<Value>(&retried, Method::Post, url, 1));
+		assert!(result.is_ok());
+		mock.assert_done();
+	}
+
+	#[test]
+	#[should_panic(expected = "polled after completion")]
+	fn response_future_panics_when_polled_after_completion() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, "{}");
+		let client = Client::with_transport(ApiKey::new("key"), mock);
+		let mut response = client.videos().id("DnJgoWDxG2A").send();
+
+		assert!((&mut response).now_or_never().unwrap().is_ok());
+		let _ = (&mut response).now_or_never();
+	}
+}
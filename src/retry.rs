@@ -0,0 +1,130 @@
+use std::{
+	collections::hash_map::RandomState,
+	hash::{BuildHasher, Hasher},
+	time::Duration,
+};
+
+use super::Error;
+
+/// policy deciding whether and when a failed request is sent again
+///
+/// Requests are retried on the errors classified as retryable by
+/// [`Error::is_retryable`](enum.Error.html#method.is_retryable), i.e. on
+/// connection errors, timeouts, server errors (5xx) and when the api reports
+/// a rate limit (429 or a `rateLimitExceeded` reason).
+/// Between attempts the request waits `base_delay * 2^(attempt - 1)`, capped
+/// at `max_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+	max_attempts: u32,
+	base_delay: Duration,
+	max_delay: Duration,
+	jitter: bool,
+	non_idempotent: bool,
+}
+
+impl RetryPolicy {
+	/// exponential backoff making at most `max_attempts` attempts in total
+	#[must_use]
+	pub fn exponential(max_attempts: u32) -> Self {
+		Self {
+			max_attempts: max_attempts.max(1),
+			base_delay: Duration::from_millis(500),
+			max_delay: Duration::from_secs(32),
+			jitter: false,
+			non_idempotent: false,
+		}
+	}
+
+	/// delay before the first retry, defaults to 500ms
+	#[must_use]
+	pub fn base_delay(mut self, base_delay: Duration) -> Self {
+		self.base_delay = base_delay;
+		self
+	}
+
+	/// upper bound for the delay between two attempts, defaults to 32s
+	#[must_use]
+	pub fn max_delay(mut self, max_delay: Duration) -> Self {
+		self.max_delay = max_delay;
+		self
+	}
+
+	/// randomize each delay between zero and its exponential value
+	#[must_use]
+	pub fn with_jitter(mut self) -> Self {
+		self.jitter = true;
+		self
+	}
+
+	/// also retry requests which are not idempotent, like inserts
+	#[must_use]
+	pub fn retry_non_idempotent(mut self) -> Self {
+		self.non_idempotent = true;
+		self
+	}
+
+	#[must_use]
+	pub fn max_attempts(&self) -> u32 {
+		self.max_attempts
+	}
+
+	pub(crate) fn allows(&self, idempotent: bool) -> bool {
+		idempotent || self.non_idempotent
+	}
+
+	pub(crate) fn should_retry(&self, attempt: u32, error: &Error) -> bool {
+		attempt < self.max_attempts && error.is_retryable()
+	}
+
+	/// delay to wait after the given (one-based) failed attempt
+	pub(crate) fn delay(&self, attempt: u32) -> Duration {
+		let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+		let delay = self
+			.base_delay
+			.checked_mul(factor)
+			.map_or(self.max_delay, |delay| delay.min(self.max_delay));
+		if self.jitter {
+			let random = RandomState::new().build_hasher().finish();
+			delay.mul_f64(random as f64 / u64::MAX as f64)
+		} else {
+			delay
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::RetryPolicy;
+
+	#[test]
+	fn delay_doubles_up_to_the_max_delay() {
+		let retry = RetryPolicy::exponential(10)
+			.base_delay(Duration::from_millis(100))
+			.max_delay(Duration::from_millis(500));
+
+		let delays = (1..=5)
+			.map(|attempt| retry.delay(attempt))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			delays,
+			[100, 200, 400, 500, 500]
+				.iter()
+				.map(|millis| Duration::from_millis(*millis))
+				.collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn jitter_stays_below_the_exponential_delay() {
+		let retry = RetryPolicy::exponential(10)
+			.base_delay(Duration::from_millis(100))
+			.with_jitter();
+
+		for attempt in 1..=5 {
+			assert!(retry.delay(attempt) <= Duration::from_millis(100) * 2u32.pow(attempt - 1));
+		}
+	}
+}
@@ -1,52 +1,164 @@
-use std::{
-	future::Future,
-	pin::Pin,
-	task::{Context, Poll},
-};
+use std::collections::{HashMap, HashSet};
 
-use chrono::{DateTime, Utc};
-use futures::future::BoxFuture;
-use log::debug;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize, Serializer};
-use snafu::{ResultExt, Snafu};
-
-use super::ApiKey;
-
-/// custom error type for the search endpoint
-#[derive(Debug, Snafu)]
-pub enum Error {
-	#[snafu(display("failed to connect to the api: {}", string))]
-	Connection { string: String },
-	#[snafu(display("failed to deserialize: {} {}", string, source))]
-	Deserialization {
-		string: String,
-		source: serde_json::Error,
-	},
-	#[snafu(display("failed to serialize: {}", source))]
-	Serialization {
-		source: serde_urlencoded::ser::Error,
-	},
-}
-
-impl From<surf::Error> for Error {
-	fn from(surf_error: surf::Error) -> Self {
-		Error::Connection {
-			string: surf_error.to_string(),
-		}
-	}
-}
+use snafu::ResultExt;
+
+pub use super::common::{PageInfo, Thumbnail, Thumbnails};
+#[cfg(feature = "chrono")]
+use super::published::Published;
+pub use super::Error;
+use super::{
+	common,
+	error::{InvalidRequest, Serialization},
+	ids, limits,
+	pagination::{self, Paginated},
+	request::{self, Request},
+	videos::{VideoResult, Videos},
+	AccessToken, ApiKey, ChannelId, Client, LanguageCode, PlaylistId, RegionCode, Timestamp,
+	VideoId,
+};
 
 /// request struct for the search endpoint
+///
+/// The filters are sent exactly as the api documents them:
+///
+/// ```rust
+/// # use yt_api::{ApiKey, Client, Request};
+/// use yt_api::search::*;
+///
+/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+/// let search = || client.search();
+/// let video_search = || client.search().item_type(ItemType::Video);
+/// let googleplex = VideoLocation::new(37.42307, -122.08427);
+///
+/// let cases = vec![
+///     (search().item_type(ItemType::Channel), "type=channel"),
+///     (search().item_type(ItemType::Playlist), "type=playlist"),
+///     (search().item_type(ItemType::Video), "type=video"),
+///     (search().channel_type(ChannelType::Any), "channelType=any"),
+///     (search().channel_type(ChannelType::Show), "channelType=show"),
+///     (video_search().event_type(EventType::Completed), "eventType=completed"),
+///     (video_search().event_type(EventType::Live), "eventType=live"),
+///     (video_search().event_type(EventType::Upcoming), "eventType=upcoming"),
+///     (search().location(googleplex.clone()).location_radius("10km"), "location=37.42307%2C-122.08427"),
+///     (search().order(SearchOrder::Date), "order=date"),
+///     (search().order(SearchOrder::Rating), "order=rating"),
+///     (search().order(SearchOrder::Relevance), "order=relevance"),
+///     (search().order(SearchOrder::Title), "order=title"),
+///     (search().order(SearchOrder::VideoCount), "order=videoCount"),
+///     (search().order(SearchOrder::ViewCount), "order=viewCount"),
+///     (search().safe_search(SafeSearch::Moderate), "safeSearch=moderate"),
+///     (search().safe_search(SafeSearch::Strict), "safeSearch=strict"),
+///     (video_search().video_definition(VideoDefinition::High), "videoDefinition=high"),
+///     (video_search().video_definition(VideoDefinition::Standard), "videoDefinition=standard"),
+///     (video_search().video_dimension(VideoDimension::Three), "videoDimension=3d"),
+///     (video_search().video_dimension(VideoDimension::Two), "videoDimension=2d"),
+///     (video_search().video_duration(VideoDuration::Long), "videoDuration=long"),
+///     (video_search().video_duration(VideoDuration::Medium), "videoDuration=medium"),
+///     (video_search().video_duration(VideoDuration::Short), "videoDuration=short"),
+///     (video_search().video_embeddable(true), "videoEmbeddable=true"),
+///     (video_search().video_embeddable(false), "videoEmbeddable=any"),
+///     (video_search().video_license(VideoLicense::CreativeCommon), "videoLicense=creativeCommon"),
+///     (video_search().video_license(VideoLicense::Youtube), "videoLicense=youtube"),
+///     (video_search().video_syndicated(true), "videoSyndicated=true"),
+///     (video_search().video_syndicated(false), "videoSyndicated=any"),
+///     (video_search().video_type(VideoType::Episode), "videoType=episode"),
+///     (video_search().video_type(VideoType::Movie), "videoType=movie"),
+///     (video_search().video_caption(VideoCaption::Any), "videoCaption=any"),
+///     (video_search().video_caption(VideoCaption::ClosedCaption), "videoCaption=closedCaption"),
+///     (video_search().video_caption(VideoCaption::None), "videoCaption=none"),
+///     (search().safe_search(SafeSearch::None), "safeSearch=none"),
+///     (video_search().video_definition(VideoDefinition::Any), "videoDefinition=any"),
+///     (video_search().video_dimension(VideoDimension::Any), "videoDimension=any"),
+///     (video_search().video_duration(VideoDuration::Any), "videoDuration=any"),
+///     (video_search().video_license(VideoLicense::Any), "videoLicense=any"),
+///     (video_search().video_type(VideoType::Any), "videoType=any"),
+/// ];
+///
+/// for (search, pair) in cases {
+///     let url = search.build_url().unwrap();
+///     assert!(url.split(|c| c == '?' || c == '&').any(|p| p == pair), "{} in {}", pair, url);
+/// }
+/// ```
+///
+/// Combinations of parameters the api rejects fail with
+/// [`Error::InvalidRequest`](../enum.Error.html#variant.InvalidRequest)
+/// before anything is sent:
+///
+/// ```rust
+/// # use yt_api::{ApiKey, Client, Error, Request};
+/// use yt_api::search::*;
+///
+/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+/// let search = || client.search();
+/// let video_search = || client.search().item_type(ItemType::Video);
+/// let googleplex = VideoLocation::new(37.42307, -122.08427);
+///
+/// let invalid = vec![
+///     (search().event_type(EventType::Live), "eventType requires type=video"),
+///     (search().video_caption(VideoCaption::ClosedCaption), "videoCaption requires type=video"),
+///     (search().video_category_id("10"), "videoCategoryId requires type=video"),
+///     (search().video_definition(VideoDefinition::High), "videoDefinition requires type=video"),
+///     (search().video_dimension(VideoDimension::Three), "videoDimension requires type=video"),
+///     (search().video_duration(VideoDuration::Short), "videoDuration requires type=video"),
+///     (search().video_embeddable(true), "videoEmbeddable requires type=video"),
+///     (search().video_license(VideoLicense::Youtube), "videoLicense requires type=video"),
+///     (search().video_syndicated(true), "videoSyndicated requires type=video"),
+///     (search().video_type(VideoType::Movie), "videoType requires type=video"),
+///     (search().item_type(ItemType::Channel).video_duration(VideoDuration::Long), "videoDuration requires type=video"),
+///     (search().for_mine(), "forMine requires type=video"),
+///     (search().related_to_video_id("DnJgoWDxG2A"), "relatedToVideoId requires type=video"),
+///     (video_search().for_developer().for_mine(), "forDeveloper cannot be combined with forMine"),
+///     (video_search().for_content_owner().for_developer(), "forContentOwner cannot be combined with forDeveloper"),
+///     (video_search().for_mine().related_to_video_id("DnJgoWDxG2A"), "forMine cannot be combined with relatedToVideoId"),
+///     (search().for_content_owner(), "forContentOwner requires onBehalfOfContentOwner"),
+///     (video_search().for_mine().video_type(VideoType::Movie), "forMine cannot be combined with videoType"),
+///     (search().location(googleplex.clone()), "location requires locationRadius"),
+///     (search().location_radius("10km"), "locationRadius requires location"),
+///     (video_search().related_to_video_id("DnJgoWDxG2A").q("rust"), "relatedToVideoId cannot be combined with q"),
+///     (video_search().related_to_video_id("DnJgoWDxG2A").order(SearchOrder::Date), "relatedToVideoId cannot be combined with order"),
+///     (video_search().related_to_video_id("DnJgoWDxG2A").event_type(EventType::Live), "relatedToVideoId cannot be combined with eventType"),
+/// ];
+/// for (search, reason) in invalid {
+///     match search.build_url() {
+///         Err(Error::InvalidRequest { reason: actual }) => assert_eq!(actual, reason),
+///         result => panic!("{} was not reported: {:?}", reason, result),
+///     }
+/// }
+///
+/// let valid = vec![
+///     search().q("rust").order(SearchOrder::Date),
+///     video_search().event_type(EventType::Live).video_duration(VideoDuration::Long),
+///     video_search().for_mine().q("rust").event_type(EventType::Completed),
+///     search().for_developer().q("rust"),
+///     search().for_content_owner().on_behalf_of_content_owner("owner"),
+///     search().location(googleplex).location_radius("10km"),
+///     video_search()
+///         .related_to_video_id("DnJgoWDxG2A")
+///         .max_results(10)
+///         .region_code("US")
+///         .relevance_language("en")
+///         .safe_search(SafeSearch::Strict),
+/// ];
+/// for search in valid {
+///     assert!(search.build_url().is_ok());
+/// }
+/// ```
+#[derive(Debug, Clone)]
 pub struct SearchList {
-	future: Option<BoxFuture<'static, Result<Response, Error>>>,
-	data: Option<SearchListData>,
+	data: SearchListData,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SearchListData {
+	#[serde(skip)]
+	client: Client,
 	key: ApiKey,
 	part: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	fields: Option<String>,
 	#[serde(skip_serializing_if = "std::ops::Not::not")]
 	for_content_owner: bool,
 	#[serde(skip_serializing_if = "std::ops::Not::not")]
@@ -54,9 +166,9 @@ struct SearchListData {
 	#[serde(skip_serializing_if = "std::ops::Not::not")]
 	for_mine: bool,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	related_to_video_id: Option<String>,
+	related_to_video_id: Option<VideoId>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	channel_id: Option<String>,
+	channel_id: Option<ChannelId>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	channel_type: Option<ChannelType>,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -70,54 +182,63 @@ struct SearchListData {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	on_behalf_of_content_owner: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	order: Option<Order>,
+	order: Option<SearchOrder>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	page_token: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	published_after: Option<DateTime<Utc>>,
+	published_after: Option<Timestamp>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	published_before: Option<DateTime<Utc>>,
+	published_before: Option<Timestamp>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	q: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	region_code: Option<String>,
+	region_code: Option<RegionCode>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	relevance_language: Option<String>,
+	relevance_language: Option<LanguageCode>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	safe_search: Option<SafeSearch>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	topic_id: Option<String>,
-	#[serde(skip_serializing_if = "Option::is_none", rename = "type")]
-	item_type: Option<ItemType>,
+	#[serde(skip)]
+	item_type: Vec<ItemType>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	video_caption: Option<String>,
+	video_caption: Option<VideoCaption>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	video_category_id: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	video_definition: Option<VideoDefinition>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	video_dimension: Option<VideoDimension>,
-	#[serde(skip_serializing_if = "std::ops::Not::not")]
-	video_embeddable: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	video_duration: Option<VideoDuration>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	video_embeddable: Option<VideoEmbeddable>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	video_license: Option<VideoLicense>,
-	#[serde(skip_serializing_if = "std::ops::Not::not")]
-	video_syndicated: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	video_syndicated: Option<VideoSyndicated>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	video_type: Option<VideoType>,
 }
 
 impl SearchList {
-	const URL: &'static str = "https://www.googleapis.com/youtube/v3/search";
+	const QUOTA_COST: u32 = 100;
+	const PATH: &'static str = "search";
 
 	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
 	#[must_use]
 	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).search()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
 		Self {
-			future: None,
-			data: Some(SearchListData {
-				key,
+			data: SearchListData {
+				key: client.key.clone(),
+				client,
 				part: String::from("snippet"),
+				fields: None,
 				for_content_owner: false,
 				for_developer: false,
 				for_mine: false,
@@ -138,305 +259,620 @@ impl SearchList {
 				relevance_language: None,
 				safe_search: None,
 				topic_id: None,
-				item_type: None,
+				item_type: Vec::new(),
 				video_caption: None,
 				video_category_id: None,
 				video_definition: None,
 				video_dimension: None,
-				video_embeddable: false,
+				video_duration: None,
+				video_embeddable: None,
 				video_license: None,
-				video_syndicated: false,
+				video_syndicated: None,
 				video_type: None,
-			}),
+			},
 		}
 	}
 
 	#[must_use]
 	pub fn for_content_owner(mut self) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.for_content_owner = true;
-		self.data = Some(data);
+		self.data.for_content_owner = true;
 		self
 	}
 
 	#[must_use]
 	pub fn for_developer(mut self) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.for_developer = true;
-		self.data = Some(data);
+		self.data.for_developer = true;
 		self
 	}
 
 	#[must_use]
 	pub fn for_mine(mut self) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.for_mine = true;
-		self.data = Some(data);
+		self.data.for_mine = true;
 		self
 	}
 
 	#[must_use]
-	pub fn related_to_video_id(mut self, related_to_video_id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.related_to_video_id = Some(related_to_video_id.into());
-		self.data = Some(data);
+	pub fn related_to_video_id(mut self, related_to_video_id: impl Into<VideoId>) -> Self {
+		self.data.related_to_video_id = Some(related_to_video_id.into());
 		self
 	}
 
+	/// only search within the videos, playlists and other resources of a
+	/// single channel
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// use yt_api::search::ChannelType;
+	///
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let url = client
+	///     .search()
+	///     .channel_id("UC_x5XG1OV2P6uZZ5FSM9Ttw")
+	///     .channel_type(ChannelType::Show)
+	///     .build_url()
+	///     .unwrap();
+	///
+	/// assert!(url.contains("&channelId=UC_x5XG1OV2P6uZZ5FSM9Ttw&channelType=show"));
+	/// ```
 	#[must_use]
-	pub fn channel_id(mut self, channel_id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.channel_id = Some(channel_id.into());
-		self.data = Some(data);
+	pub fn channel_id(mut self, channel_id: impl Into<ChannelId>) -> Self {
+		self.data.channel_id = Some(channel_id.into());
 		self
 	}
 
 	#[must_use]
 	pub fn channel_type(mut self, channel_type: impl Into<ChannelType>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.channel_type = Some(channel_type.into());
-		self.data = Some(data);
+		self.data.channel_type = Some(channel_type.into());
 		self
 	}
 
 	#[must_use]
 	pub fn event_type(mut self, event_type: impl Into<EventType>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.event_type = Some(event_type.into());
-		self.data = Some(data);
+		self.data.event_type = Some(event_type.into());
 		self
 	}
 
 	#[must_use]
 	pub fn location(mut self, location: impl Into<VideoLocation>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.location = Some(location.into());
-		self.data = Some(data);
+		self.data.location = Some(location.into());
 		self
 	}
 
 	#[must_use]
 	pub fn location_radius(mut self, location_radius: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.location_radius = Some(location_radius.into());
-		self.data = Some(data);
+		self.data.location_radius = Some(location_radius.into());
 		self
 	}
 
 	#[must_use]
 	pub fn max_results(mut self, max_results: impl Into<u8>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.max_results = Some(max_results.into());
-		self.data = Some(data);
+		self.data.max_results = Some(max_results.into());
 		self
 	}
 
+	/// act on behalf of the content owner with the given id, only usable by
+	/// youtube content partners authorized with an
+	/// [`access_token`](#method.access_token) of a cms account linked to it
 	#[must_use]
 	pub fn on_behalf_of_content_owner(
 		mut self,
 		on_behalf_of_content_owner: impl Into<String>,
 	) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
-		self.data = Some(data);
+		self.data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
 		self
 	}
 
 	#[must_use]
-	pub fn order(mut self, order: impl Into<Order>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.order = Some(order.into());
-		self.data = Some(data);
+	pub fn order(mut self, order: impl Into<SearchOrder>) -> Self {
+		self.data.order = Some(order.into());
 		self
 	}
 
 	#[must_use]
 	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.page_token = Some(page_token.into());
-		self.data = Some(data);
+		self.data.page_token = Some(page_token.into());
 		self
 	}
 
 	#[must_use]
-	pub fn published_after(mut self, published_after: impl Into<DateTime<Utc>>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.published_after = Some(published_after.into());
-		self.data = Some(data);
+	pub fn published_after(mut self, published_after: impl Into<Timestamp>) -> Self {
+		self.data.published_after = Some(published_after.into());
 		self
 	}
 
 	#[must_use]
-	pub fn published_before(mut self, published_before: impl Into<DateTime<Utc>>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.published_before = Some(published_before.into());
-		self.data = Some(data);
+	pub fn published_before(mut self, published_before: impl Into<Timestamp>) -> Self {
+		self.data.published_before = Some(published_before.into());
 		self
 	}
 
 	#[must_use]
 	pub fn q(mut self, q: impl Into<String>) -> Self {
-		let mut data = self.data.unwrap();
-		data.q = Some(q.into());
-		self.data = Some(data);
+		self.data.q = Some(q.into());
 		self
 	}
 
 	#[must_use]
-	pub fn region_code(mut self, region_code: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.region_code = Some(region_code.into());
-		self.data = Some(data);
+	pub fn region_code(mut self, region_code: impl Into<RegionCode>) -> Self {
+		self.data.region_code = Some(region_code.into());
 		self
 	}
 
+	/// prefer results in the given language, results in other languages are
+	/// still returned when they are relevant enough
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let url = client
+	///     .search()
+	///     .q("rust lang")
+	///     .region_code("DE")
+	///     .relevance_language("de")
+	///     .build_url()
+	///     .unwrap();
+	///
+	/// assert!(url.contains("&regionCode=DE&relevanceLanguage=de"));
+	/// ```
 	#[must_use]
-	pub fn relevance_language(mut self, relevance_language: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.relevance_language = Some(relevance_language.into());
-		self.data = Some(data);
+	pub fn relevance_language(mut self, relevance_language: impl Into<LanguageCode>) -> Self {
+		self.data.relevance_language = Some(relevance_language.into());
 		self
 	}
 
 	#[must_use]
 	pub fn safe_search(mut self, safe_search: impl Into<SafeSearch>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.safe_search = Some(safe_search.into());
-		self.data = Some(data);
+		self.data.safe_search = Some(safe_search.into());
 		self
 	}
 
 	#[must_use]
 	pub fn topic_id(mut self, topic_id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.topic_id = Some(topic_id.into());
-		self.data = Some(data);
+		self.data.topic_id = Some(topic_id.into());
 		self
 	}
 
 	#[must_use]
 	pub fn item_type(mut self, item_type: impl Into<ItemType>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.item_type = Some(item_type.into());
-		self.data = Some(data);
+		self.data.item_type = vec![item_type.into()];
+		self
+	}
+
+	/// search for multiple kinds of resources at once, sent as one comma
+	/// separated `type` parameter
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// use yt_api::search::ItemType;
+	///
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let url = client.search().q("rust").types(vec![ItemType::Video, ItemType::Playlist]).build_url().unwrap();
+	/// assert!(url.ends_with("&type=video,playlist"));
+	/// ```
+	#[must_use]
+	pub fn types<I>(mut self, types: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<ItemType>,
+	{
+		self.data.item_type = types.into_iter().map(Into::into).collect();
 		self
 	}
 
 	#[must_use]
-	pub fn video_caption(mut self, video_caption: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_caption = Some(video_caption.into());
-		self.data = Some(data);
+	pub fn video_caption(mut self, video_caption: impl Into<VideoCaption>) -> Self {
+		self.data.video_caption = Some(video_caption.into());
 		self
 	}
 
 	#[must_use]
 	pub fn video_category_id(mut self, video_category_id: impl Into<String>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_category_id = Some(video_category_id.into());
-		self.data = Some(data);
+		self.data.video_category_id = Some(video_category_id.into());
 		self
 	}
 
 	#[must_use]
 	pub fn video_definition(mut self, video_definition: impl Into<VideoDefinition>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_definition = Some(video_definition.into());
-		self.data = Some(data);
+		self.data.video_definition = Some(video_definition.into());
 		self
 	}
 
 	#[must_use]
 	pub fn video_dimension(mut self, video_dimension: impl Into<VideoDimension>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_dimension = Some(video_dimension.into());
-		self.data = Some(data);
+		self.data.video_dimension = Some(video_dimension.into());
 		self
 	}
 
+	/// only return videos which can be embedded into a webpage, requires
+	/// [`item_type(ItemType::Video)`](#method.item_type)
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Error, Request};
+	/// use yt_api::search::ItemType;
+	///
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let search = client.search().q("rust lang").video_embeddable(true);
+	/// assert!(matches!(search.build_url(), Err(Error::InvalidRequest { .. })));
+	///
+	/// let url = search.item_type(ItemType::Video).build_url().unwrap();
+	/// assert!(url.contains("&videoEmbeddable=true&") && url.ends_with("&type=video"));
+	/// ```
 	#[must_use]
-	pub fn video_embeddable(mut self) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_embeddable = true;
-		self.data = Some(data);
+	pub fn video_embeddable(mut self, video_embeddable: impl Into<VideoEmbeddable>) -> Self {
+		self.data.video_embeddable = Some(video_embeddable.into());
+		self
+	}
+
+	/// only return videos of a length of less than 4 minutes (`Short`),
+	/// between 4 and 20 minutes (`Medium`) or more than 20 minutes (`Long`)
+	#[must_use]
+	pub fn video_duration(mut self, video_duration: impl Into<VideoDuration>) -> Self {
+		self.data.video_duration = Some(video_duration.into());
 		self
 	}
 
 	#[must_use]
 	pub fn video_license(mut self, video_license: impl Into<VideoLicense>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_license = Some(video_license.into());
-		self.data = Some(data);
+		self.data.video_license = Some(video_license.into());
 		self
 	}
 
+	/// only return videos which can be played outside of youtube.com,
+	/// requires [`item_type(ItemType::Video)`](#method.item_type)
 	#[must_use]
-	pub fn video_syndicated(mut self) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_syndicated = true;
-		self.data = Some(data);
+	pub fn video_syndicated(mut self, video_syndicated: impl Into<VideoSyndicated>) -> Self {
+		self.data.video_syndicated = Some(video_syndicated.into());
 		self
 	}
 
 	#[must_use]
 	pub fn video_type(mut self, video_type: impl Into<VideoType>) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.video_type = Some(video_type.into());
-		self.data = Some(data);
-		self
-	}
-}
-
-impl Future for SearchList {
-	type Output = Result<Response, Error>;
-
-	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-		if self.future.is_none() {
-			let data = self.data.take().unwrap();
-			self.future = Some(Box::pin(async move {
-				let url = format!(
-					"{}?{}",
-					Self::URL,
-					serde_urlencoded::to_string(&data).context(Serialization)?
-				);
-				debug!("getting {}", url);
-				let response = surf::get(&url).recv_string().await?;
-				serde_json::from_str(&response)
-					.with_context(move || Deserialization { string: response })
-			}));
+		self.data.video_type = Some(video_type.into());
+		self
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	///
+	/// Bookkeeping fields like `kind`, `etag` and `pageInfo` are optional as
+	/// well, a response missing them or any items is still successful:
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let response = client.search().q("rust lang").fields("items(id/videoId,snippet/title)").await.unwrap();
+	/// for item in response.items {
+	///     println!("{:?}: {:?}", item.id.video_id, item.snippet.title);
+	/// }
+	/// # });
+	/// ```
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+
+	/// send the search and look up its videos with `videos`, e.g.
+	/// `client.videos().statistics()`, for the parts a search result lacks
+	/// like the duration, the statistics or the exact publish time
+	///
+	/// The videos are returned in the order of the search results, the ids
+	/// of `videos` are replaced by the ids found while its other parameters
+	/// are kept. Fails if either request fails.
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let hydrated = client.search().q("rust lang").hydrate(client.videos().statistics()).await.unwrap();
+	/// for video in hydrated.videos {
+	///     println!("{:?} {:?}", video.snippet.title, video.content_details.duration);
+	/// }
+	/// # });
+	/// ```
+	pub async fn hydrate(self, videos: Videos) -> Result<Hydrated, Error> {
+		let response = self.send().await?;
+		let mut seen = HashSet::new();
+		let ids = response
+			.items
+			.iter()
+			.filter_map(|item| item.id.video_id.clone())
+			.filter(|id| seen.insert(id.clone()))
+			.collect::<Vec<_>>();
+		let mut found = HashMap::new();
+		let mut batches = videos.batches(ids, 1);
+		while let Some(batch) = batches.next().await {
+			found.extend(
+				batch
+					.result?
+					.into_iter()
+					.map(|video| (video.id.clone(), video)),
+			);
+		}
+		let mut hydrated = Hydrated {
+			next_page_token: response.next_page_token,
+			..Hydrated::default()
+		};
+		for item in response.items {
+			match &item.id.video_id {
+				Some(id) => match found.get(id) {
+					Some(video) => hydrated.videos.push(video.clone()),
+					None => hydrated.missing.push(id.clone()),
+				},
+				None => hydrated.others.push(item),
+			}
+		}
+		Ok(hydrated)
+	}
+}
+
+/// page of a search with its videos looked up, see
+/// [`SearchList::hydrate`](struct.SearchList.html#method.hydrate)
+#[derive(Debug, Clone, Default)]
+pub struct Hydrated {
+	/// the videos found by the search, in the order of the search results
+	pub videos: Vec<VideoResult>,
+	/// the ids of the videos found by the search which were not returned by
+	/// the lookup, e.g. because they were deleted in the meantime
+	pub missing: Vec<VideoId>,
+	/// the channels and playlists found by the search
+	pub others: Vec<SearchResult>,
+	/// token of the next page of the search
+	pub next_page_token: Option<String>,
+}
+
+impl SearchListData {
+	fn validate(&self) -> Result<(), Error> {
+		if let Some(region_code) = &self.region_code {
+			region_code.validate()?;
+		}
+		if let Some(relevance_language) = &self.relevance_language {
+			relevance_language.validate("relevanceLanguage")?;
 		}
+		limits::check(
+			"maxResults",
+			&limits::MAX_RESULTS,
+			self.max_results.map(u16::from),
+		)?;
+		if let Some(location) = &self.location {
+			limits::check("location", &limits::LATITUDE, Some(location.latitude))?;
+			limits::check("location", &limits::LONGITUDE, Some(location.longitude))?;
+		}
+		if let Some(location_radius) = &self.location_radius {
+			limits::check_location_radius(location_radius)?;
+		}
+		self.check_rules()
+	}
 
-		self.future.as_mut().unwrap().as_mut().poll(cx)
+	/// rules of the api on how the parameters may be combined
+	fn check_rules(&self) -> Result<(), Error> {
+		let video_filters = [
+			("eventType", self.event_type.is_some()),
+			("videoCaption", self.video_caption.is_some()),
+			("videoCategoryId", self.video_category_id.is_some()),
+			("videoDefinition", self.video_definition.is_some()),
+			("videoDimension", self.video_dimension.is_some()),
+			("videoDuration", self.video_duration.is_some()),
+			("videoEmbeddable", self.video_embeddable.is_some()),
+			("videoLicense", self.video_license.is_some()),
+			("videoSyndicated", self.video_syndicated.is_some()),
+			("videoType", self.video_type.is_some()),
+		];
+		let special_filters = [
+			("forContentOwner", self.for_content_owner),
+			("forDeveloper", self.for_developer),
+			("forMine", self.for_mine),
+			("relatedToVideoId", self.related_to_video_id.is_some()),
+		];
+		let is_video_search = self.item_type == [ItemType::Video];
+		for (filter, set) in video_filters.iter().chain(&special_filters[2..]) {
+			require(filter, *set, "type=video", is_video_search)?;
+		}
+		for (index, (filter, set)) in special_filters.iter().enumerate() {
+			exclude(filter, *set, &special_filters[index + 1..])?;
+		}
+		require(
+			"forContentOwner",
+			self.for_content_owner,
+			"onBehalfOfContentOwner",
+			self.on_behalf_of_content_owner.is_some(),
+		)?;
+		exclude("forMine", self.for_mine, &video_filters[3..])?;
+		let location = self.location.is_some();
+		let location_radius = self.location_radius.is_some();
+		require("location", location, "locationRadius", location_radius)?;
+		require("locationRadius", location_radius, "location", location)?;
+		let other_filters = [
+			("channelId", self.channel_id.is_some()),
+			("channelType", self.channel_type.is_some()),
+			("location", location),
+			("locationRadius", location_radius),
+			(
+				"onBehalfOfContentOwner",
+				self.on_behalf_of_content_owner.is_some(),
+			),
+			("order", self.order.is_some()),
+			("publishedAfter", self.published_after.is_some()),
+			("publishedBefore", self.published_before.is_some()),
+			("q", self.q.is_some()),
+			("topicId", self.topic_id.is_some()),
+		];
+		let related = self.related_to_video_id.is_some();
+		exclude("relatedToVideoId", related, &other_filters)?;
+		exclude("relatedToVideoId", related, &video_filters)
 	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ChannelType {
-	Any,
-	Show,
+/// fails if `filter` is set without the `required` parameter
+fn require(filter: &str, set: bool, required: &str, present: bool) -> Result<(), Error> {
+	if set && !present {
+		return InvalidRequest {
+			reason: format!("{} requires {}", filter, required),
+		}
+		.fail();
+	}
+	Ok(())
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum EventType {
-	Completed,
-	Live,
-	Upcoming,
+/// fails if `filter` is set together with any of `others`
+fn exclude(filter: &str, set: bool, others: &[(&str, bool)]) -> Result<(), Error> {
+	match others.iter().find(|(_, other_set)| set && *other_set) {
+		Some((other, _)) => InvalidRequest {
+			reason: format!("{} cannot be combined with {}", filter, other),
+		}
+		.fail(),
+		None => Ok(()),
+	}
 }
 
+impl Request for SearchList {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.validate()?;
+		let mut query = serde_urlencoded::to_string(data).context(Serialization)?;
+		ids::append_comma_separated(&mut query, "type", &data.item_type);
+		Ok(data.client.url(Self::PATH, &query))
+	}
+}
+
+request::request_methods!(SearchList => Response, "youtube#searchListResponse");
+
+impl Paginated for SearchList {
+	type Response = Response;
+
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
+}
+
+/// defines an enum of the values of the api parameter `$parameter`, whose
+/// strings are shared by `Serialize`, `Display` and `FromStr`
+///
+/// Parsing ignores the case and fails with [`Error::InvalidParameter`] listing
+/// the valid values.
+macro_rules! parameter_enum {
+	(
+		$(#[$meta:meta])*
+		$name:ident($parameter:literal) {
+			$($variant:ident => $value:literal,)*
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		pub enum $name {
+			$($variant,)*
+		}
+
+		impl $name {
+			/// all variants in the order of their declaration
+			pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+			/// the string the api uses for the variant
+			#[must_use]
+			pub fn as_str(self) -> &'static str {
+				match self {
+					$(Self::$variant => $value,)*
+				}
+			}
+		}
+
+		impl serde::Serialize for $name {
+			fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+			where
+				S: serde::Serializer,
+			{
+				serializer.serialize_str(self.as_str())
+			}
+		}
+
+		impl std::fmt::Display for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				f.write_str(self.as_str())
+			}
+		}
+
+		impl std::str::FromStr for $name {
+			type Err = $crate::Error;
+
+			fn from_str(input: &str) -> Result<Self, $crate::Error> {
+				Self::ALL
+					.iter()
+					.copied()
+					.find(|variant| variant.as_str().eq_ignore_ascii_case(input.trim()))
+					.ok_or_else(|| $crate::Error::InvalidParameter {
+						field: String::from($parameter),
+						message: format!(
+							"{:?} is none of {}",
+							input,
+							Self::ALL
+								.iter()
+								.map(|variant| variant.as_str())
+								.collect::<Vec<_>>()
+								.join(", ")
+						),
+					})
+			}
+		}
+	};
+}
+
+pub(crate) use parameter_enum;
+
+parameter_enum! {
+	ChannelType("channelType") {
+		Any => "any",
+		Show => "show",
+	}
+}
+
+parameter_enum! {
+	EventType("eventType") {
+		Completed => "completed",
+		Live => "live",
+		Upcoming => "upcoming",
+	}
+}
+
+/// center of a search by [`location`](struct.SearchList.html#method.location),
+/// sent as `latitude,longitude`
+///
+/// Coordinates out of range fail with [`Error::InvalidParameter`] when the
+/// url is built, which catches most swapped coordinates:
+///
+/// ```rust
+/// # use yt_api::{search::VideoLocation, ApiKey, Client, Request};
+/// let client = Client::new(ApiKey::new("key"));
+/// let search = |location| client.search().location(location).location_radius("1km");
+///
+/// let sydney_opera_house = VideoLocation::new(-33.8568, 151.2153);
+/// assert!(search(sydney_opera_house).build_url().unwrap().contains("location=-33.8568%2C151.2153&"));
+/// assert!(search(VideoLocation::new(151.2153, -33.8568)).build_url().is_err());
+/// ```
 #[derive(Debug, Clone)]
 pub struct VideoLocation {
-	longitude: f32,
 	latitude: f32,
+	longitude: f32,
 }
 
 impl VideoLocation {
 	#[must_use]
-	pub fn new(longitude: f32, latitude: f32) -> Self {
+	pub fn new(latitude: f32, longitude: f32) -> Self {
 		Self {
-			longitude,
 			latitude,
+			longitude,
 		}
 	}
 }
@@ -446,139 +882,437 @@ impl Serialize for VideoLocation {
 	where
 		S: Serializer,
 	{
-		serializer.serialize_str(&format!("{},{}", self.longitude, self.latitude))
+		serializer.serialize_str(&format!("{},{}", self.latitude, self.longitude))
 	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum Order {
-	Date,
-	Rating,
-	Relevance,
-	Title,
-	VideoCount,
-	ViewCount,
+parameter_enum! {
+	/// order of the results
+	///
+	/// These are the values of the search endpoint only, `videos` and
+	/// `playlist_items` just re-export the type for compatibility. The
+	/// commentThreads endpoint has its own
+	/// [`CommentThreadOrder`](../commentthreads/enum.CommentThreadOrder.html).
+	///
+	/// ```rust
+	/// # use yt_api::search::SearchOrder;
+	/// assert_eq!("viewCount".parse::<SearchOrder>().unwrap(), SearchOrder::ViewCount);
+	/// assert_eq!("VIEWCOUNT".parse::<SearchOrder>().unwrap(), SearchOrder::ViewCount);
+	/// assert_eq!(SearchOrder::VideoCount.to_string(), "videoCount");
+	///
+	/// let error = "views".parse::<SearchOrder>().unwrap_err();
+	/// assert_eq!(
+	///     error.to_string(),
+	///     r#"invalid order: "views" is none of date, rating, relevance, title, videoCount, viewCount"#,
+	/// );
+	/// ```
+	///
+	/// All parameter enums convert from and to the same strings they are
+	/// sent as:
+	///
+	/// ```rust
+	/// # use std::{fmt::{Debug, Display}, str::FromStr};
+	/// # use serde::Serialize;
+	/// use yt_api::search::*;
+	///
+	/// fn round_trip<T>(variants: &[T])
+	/// where
+	///     T: Copy + Debug + Display + FromStr + PartialEq + Serialize,
+	///     T::Err: Debug,
+	/// {
+	///     for variant in variants {
+	///         let string = variant.to_string();
+	///         assert_eq!(string.parse::<T>().unwrap(), *variant);
+	///         assert_eq!(string.to_uppercase().parse::<T>().unwrap(), *variant);
+	///         assert_eq!(serde_json::to_value(variant).unwrap(), string.as_str());
+	///     }
+	/// }
+	///
+	/// round_trip(ChannelType::ALL);
+	/// round_trip(EventType::ALL);
+	/// round_trip(SearchOrder::ALL);
+	/// round_trip(SafeSearch::ALL);
+	/// round_trip(ItemType::ALL);
+	/// round_trip(VideoCaption::ALL);
+	/// round_trip(VideoDefinition::ALL);
+	/// round_trip(VideoDimension::ALL);
+	/// round_trip(VideoDuration::ALL);
+	/// round_trip(VideoEmbeddable::ALL);
+	/// round_trip(VideoLicense::ALL);
+	/// round_trip(VideoSyndicated::ALL);
+	/// round_trip(VideoType::ALL);
+	/// ```
+	SearchOrder("order") {
+		Date => "date",
+		Rating => "rating",
+		Relevance => "relevance",
+		Title => "title",
+		VideoCount => "videoCount",
+		ViewCount => "viewCount",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum SafeSearch {
-	Moderate,
-	Strict,
+/// the former name of [`SearchOrder`](enum.SearchOrder.html)
+#[deprecated(note = "renamed to `SearchOrder`")]
+pub type Order = SearchOrder;
+
+parameter_enum! {
+	SafeSearch("safeSearch") {
+		Moderate => "moderate",
+		None => "none",
+		Strict => "strict",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ItemType {
-	Channel,
-	Playlist,
-	Video,
+parameter_enum! {
+	/// kind of the results, sent as the parameter `type`
+	ItemType("type") {
+		Channel => "channel",
+		Playlist => "playlist",
+		Video => "video",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoCaption {
-	ClosedCaption,
-	None,
+parameter_enum! {
+	VideoCaption("videoCaption") {
+		Any => "any",
+		ClosedCaption => "closedCaption",
+		None => "none",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoDefinition {
-	High,
-	Standard,
+parameter_enum! {
+	VideoDefinition("videoDefinition") {
+		Any => "any",
+		High => "high",
+		Standard => "standard",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub enum VideoDimension {
-	#[serde(rename = "3d")]
-	Three,
-	#[serde(rename = "2d")]
-	Two,
+parameter_enum! {
+	VideoDimension("videoDimension") {
+		Any => "any",
+		Three => "3d",
+		Two => "2d",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoDuration {
-	Long,
-	Medium,
-	Short,
+parameter_enum! {
+	VideoDuration("videoDuration") {
+		Any => "any",
+		Long => "long",
+		Medium => "medium",
+		Short => "short",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoLicense {
-	CreativeCommon,
-	Youtube,
+parameter_enum! {
+	/// whether only embeddable videos are returned, a `bool` converts to `True`
+	/// or `Any`
+	VideoEmbeddable("videoEmbeddable") {
+		Any => "any",
+		True => "true",
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoType {
-	Episode,
-	Movie,
+impl From<bool> for VideoEmbeddable {
+	fn from(embeddable: bool) -> Self {
+		if embeddable {
+			Self::True
+		} else {
+			Self::Any
+		}
+	}
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+parameter_enum! {
+	VideoLicense("videoLicense") {
+		Any => "any",
+		CreativeCommon => "creativeCommon",
+		Youtube => "youtube",
+	}
+}
+
+parameter_enum! {
+	/// whether only videos playable outside of youtube.com are returned, a
+	/// `bool` converts to `True` or `Any`
+	VideoSyndicated("videoSyndicated") {
+		Any => "any",
+		True => "true",
+	}
+}
+
+impl From<bool> for VideoSyndicated {
+	fn from(syndicated: bool) -> Self {
+		if syndicated {
+			Self::True
+		} else {
+			Self::Any
+		}
+	}
+}
+
+parameter_enum! {
+	VideoType("videoType") {
+		Any => "any",
+		Episode => "episode",
+		Movie => "movie",
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Response {
 	pub kind: String,
 	pub etag: String,
+	pub next_page_token: Option<String>,
 	pub prev_page_token: Option<String>,
 	pub region_code: String,
 	pub page_info: PageInfo,
 	pub items: Vec<SearchResult>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PageInfo {
-	pub total_results: i64,
-	pub results_per_page: i64,
-}
+pagination::page_methods!(Response => SearchResult);
 
-#[derive(Debug, Clone, Deserialize)]
+/// result of a [`Response`](struct.Response.html), values and fields unknown
+/// to this crate are accepted
+///
+/// ```rust
+/// # use yt_api::search::Response;
+/// let response: Response = serde_json::from_str(r#"{
+///     "items": [{
+///         "kind": "youtube#searchResult",
+///         "id": { "kind": "youtube#short", "shortId": "DnJgoWDxG2A" },
+///         "snippet": { "title": "title", "liveBroadcastContent": "premiere" }
+///     }]
+/// }"#).unwrap();
+///
+/// assert_eq!(response.items[0].id.kind, "youtube#short");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct SearchResult {
 	pub kind: String,
 	pub etag: String,
 	pub id: Id,
 	pub snippet: Snippet,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[cfg(feature = "chrono")]
+impl Published for SearchResult {
+	fn published_at(&self) -> Option<Timestamp> {
+		self.snippet.published_at
+	}
+}
+
+/// id of the resource a [`SearchResult`](struct.SearchResult.html) refers
+/// to, which of the ids is set depends on the `kind`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Id {
 	pub kind: String,
-	pub video_id: Option<String>,
-	pub channel_id: Option<String>,
-	pub playlist_id: Option<String>,
+	pub video_id: Option<VideoId>,
+	pub channel_id: Option<ChannelId>,
+	pub playlist_id: Option<PlaylistId>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+impl Id {
+	/// the id of the video, channel or playlist, `None` for kinds of
+	/// resources unknown to this crate
+	///
+	/// ```rust
+	/// # use yt_api::search::{Response, ResourceId};
+	/// let response: Response = serde_json::from_str(r#"{
+	///     "items": [
+	///         { "id": { "kind": "youtube#video", "videoId": "DnJgoWDxG2A" } },
+	///         { "id": { "kind": "youtube#channel", "channelId": "UC_x5XG1OV2P6uZZ5FSM9Ttw" } },
+	///         { "id": { "kind": "youtube#playlist", "playlistId": "PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL" } },
+	///         { "id": { "kind": "youtube#short", "shortId": "DnJgoWDxG2A" } }
+	///     ]
+	/// }"#).unwrap();
+	///
+	/// let ids = response.items.iter().map(|item| item.id.resource_id()).collect::<Vec<_>>();
+	/// assert_eq!(ids[0], Some(ResourceId::Video("DnJgoWDxG2A".into())));
+	/// assert_eq!(ids[1], Some(ResourceId::Channel("UC_x5XG1OV2P6uZZ5FSM9Ttw".into())));
+	/// assert_eq!(ids[2], Some(ResourceId::Playlist("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL".into())));
+	/// assert_eq!(ids[3], None);
+	/// ```
+	#[must_use]
+	pub fn resource_id(&self) -> Option<ResourceId> {
+		match self.kind.as_str() {
+			"youtube#video" => self.video_id.clone().map(ResourceId::Video),
+			"youtube#channel" => self.channel_id.clone().map(ResourceId::Channel),
+			"youtube#playlist" => self.playlist_id.clone().map(ResourceId::Playlist),
+			_ => None,
+		}
+	}
+}
+
+/// id of a video, channel or playlist found by a search
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ResourceId {
+	Video(VideoId),
+	Channel(ChannelId),
+	Playlist(PlaylistId),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Snippet {
-	pub published_at: Option<DateTime<Utc>>,
-	pub channel_id: Option<String>,
+	pub published_at: Option<Timestamp>,
+	pub channel_id: Option<ChannelId>,
 	pub title: Option<String>,
 	pub description: Option<String>,
 	pub thumbnails: Option<Thumbnails>,
 	pub channel_title: Option<String>,
 	pub live_broadcast_content: Option<String>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct Thumbnails {
-	pub default: Option<Thumbnail>,
-	pub medium: Option<Thumbnail>,
-	pub high: Option<Thumbnail>,
-	pub standard: Option<Thumbnail>,
-	pub maxres: Option<Thumbnail>,
+impl Snippet {
+	/// the title with the html entities left in it by the api decoded, see
+	/// [`description_plain`](#method.description_plain)
+	#[must_use]
+	pub fn title_plain(&self) -> Option<String> {
+		self.title.as_deref().map(common::decode_entities)
+	}
+
+	/// the description with the html entities left in it by the api decoded,
+	/// e.g. `&#39;` to `'`, for displaying it as plain text
+	///
+	/// ```rust
+	/// # use yt_api::search::Snippet;
+	/// let snippet = |description: &str| Snippet {
+	///     description: Some(String::from(description)),
+	///     ..Default::default()
+	/// };
+	///
+	/// let decoded = [
+	///     ("Rust &amp; WebAssembly", "Rust & WebAssembly"),
+	///     ("&quot;Hello, World!&quot;", "\"Hello, World!\""),
+	///     ("Rust&#39;s ownership", "Rust's ownership"),
+	///     ("Vec&lt;T&gt; &#x2014; explained", "Vec<T> — explained"),
+	///     // only decoded once
+	///     ("&amp;quot;", "&quot;"),
+	///     // not an entity
+	///     ("R&D; Tom & Jerry &unknown;", "R&D; Tom & Jerry &unknown;"),
+	///     ("no entities", "no entities"),
+	/// ];
+	/// for (description, plain) in decoded.iter() {
+	///     assert_eq!(snippet(description).description_plain().as_deref(), Some(*plain));
+	/// }
+	/// assert_eq!(Snippet::default().description_plain(), None);
+	/// ```
+	#[must_use]
+	pub fn description_plain(&self) -> Option<String> {
+		self.description.as_deref().map(common::decode_entities)
+	}
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct Thumbnail {
-	pub url: String,
-	pub width: Option<u64>,
-	pub height: Option<u64>,
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::{testing::MockTransport, ApiKey, Client};
+
+	#[test]
+	fn fields_leave_out_the_bookkeeping_fields() {
+		let mock = Arc::new(MockTransport::new());
+		// response to `fields=items(id/videoId,snippet/title)`
+		mock.expect(
+			"/search?",
+			200,
+			r#"{ "items": [{ "id": { "videoId": "DnJgoWDxG2A" }, "snippet": { "title": "title" } }] }"#,
+		);
+		let client = Client::with_transport(ApiKey::new("key"), mock);
+
+		let request = client
+			.search()
+			.q("rust lang")
+			.fields("items(id/videoId,snippet/title)");
+		let response = futures::executor::block_on(request.send()).unwrap();
+		assert_eq!(
+			response.items[0].id.video_id.as_ref().unwrap().as_str(),
+			"DnJgoWDxG2A"
+		);
+		assert_eq!(response.kind, "");
+		assert_eq!(response.page_info.total_results, 0);
+	}
+
+	#[test]
+	fn search_without_results_succeeds() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"/search?",
+			200,
+			r#"{
+				"kind": "youtube#searchListResponse",
+				"etag": "etag",
+				"regionCode": "NL",
+				"pageInfo": { "totalResults": 0, "resultsPerPage": 5 }
+			}"#,
+		);
+		let client = Client::with_transport(ApiKey::new("key"), mock);
+
+		let response = futures::executor::block_on(client.search().q("zxqv").send()).unwrap();
+		assert!(response.is_empty());
+		assert_eq!(response.next_page_token, None);
+	}
+
+	#[test]
+	fn hydrate_keeps_the_order_of_the_search() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"/search?",
+			200,
+			r#"{ "nextPageToken": "CAQQAA", "items": [
+				{ "id": { "kind": "youtube#video", "videoId": "DnJgoWDxG2A" } },
+				{ "id": { "kind": "youtube#channel", "channelId": "UC_x5XG1OV2P6uZZ5FSM9Ttw" } },
+				{ "id": { "kind": "youtube#video", "videoId": "deletedXXXX" } },
+				{ "id": { "kind": "youtube#video", "videoId": "dQw4w9WgXcQ" } }
+			] }"#,
+		)
+		// the videos in another order than they were asked for
+		.expect(
+			"&id=DnJgoWDxG2A,deletedXXXX,dQw4w9WgXcQ",
+			200,
+			r#"{ "items": [
+				{ "id": "dQw4w9WgXcQ", "contentDetails": { "duration": "PT4M13S" } },
+				{ "id": "DnJgoWDxG2A", "contentDetails": { "duration": "PT4M13S" } }
+			] }"#,
+		);
+		let client = Client::with_transport(ApiKey::new("key"), mock.clone());
+
+		let request = client
+			.search()
+			.q("rust lang")
+			.hydrate(client.videos().statistics());
+		let hydrated = futures::executor::block_on(request).unwrap();
+
+		let ids = hydrated
+			.videos
+			.iter()
+			.map(|video| video.id.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(ids, ["DnJgoWDxG2A", "dQw4w9WgXcQ"]);
+		assert_eq!(
+			hydrated.videos[0].content_details.duration.as_deref(),
+			Some("PT4M13S")
+		);
+		// deleted between the search and the lookup
+		assert_eq!(hydrated.missing, ["deletedXXXX"]);
+		assert_eq!(
+			hydrated.others[0].id.channel_id.as_ref().unwrap(),
+			"UC_x5XG1OV2P6uZZ5FSM9Ttw"
+		);
+		assert_eq!(hydrated.next_page_token.as_deref(), Some("CAQQAA"));
+		mock.assert_done();
+	}
 }
@@ -0,0 +1,327 @@
+//! helpers to test applications using this crate without sending requests
+//! to youtube
+//!
+//! A [`MockTransport`](struct.MockTransport.html) answers the requests of a
+//! [`Client`](../struct.Client.html) with canned responses. The response
+//! types implement `Default` and serialize to the json of the api, so
+//! fixtures can be built in code:
+//!
+//! ```rust
+//! # use std::sync::Arc;
+//! # use yt_api::{testing::MockTransport, videos, ApiKey, Client};
+//! #
+//! # futures::executor::block_on(async {
+//! let mock = Arc::new(MockTransport::new());
+//! mock.expect_json(
+//!     "/videos?",
+//!     &videos::Response {
+//!         items: vec![videos::VideoResult {
+//!             id: "DnJgoWDxG2A".into(),
+//!             snippet: videos::Snippet {
+//!                 title: Some(String::from("Rust in 100 Seconds")),
+//!                 ..Default::default()
+//!             },
+//!             ..Default::default()
+//!         }],
+//!         ..Default::default()
+//!     },
+//! );
+//! mock.expect("/search?", 403, r#"{ "error": { "code": 403, "message": "quota exceeded" } }"#);
+//!
+//! let client = Client::with_transport(ApiKey::new("key"), mock.clone());
+//! let response = client.videos().id("DnJgoWDxG2A").await.unwrap();
+//! assert_eq!(response.items[0].snippet.title.as_deref(), Some("Rust in 100 Seconds"));
+//! assert!(client.search().q("rust").await.is_err());
+//! // nothing was expected for a second videos request
+//! assert!(client.videos().id("DnJgoWDxG2A").await.is_err());
+//!
+//! let requests = mock.requests();
+//! assert_eq!(requests.len(), 3);
+//! assert!(requests[0].url.ends_with("&id=DnJgoWDxG2A"));
+//! mock.assert_done();
+//! # });
+//! ```
+//!
+//! A [`Cassette`](struct.Cassette.html) records the responses of another
+//! transport to a file and replays them later, e.g. to run tests against
+//! real responses without network access.
+
+use std::{
+	collections::VecDeque,
+	fs, io,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+	observer::redact,
+	transport::{HttpRequest, HttpResponse, Method, Transport, TransportError, TransportFuture},
+};
+
+/// [`Transport`](../transport/trait.Transport.html) answering requests with
+/// the responses expected for them and recording every request
+///
+/// A request is answered with the oldest expectation whose pattern is part
+/// of its url, each expectation is used once. Requests without a matching
+/// expectation fail with a connection error.
+#[derive(Debug, Default)]
+pub struct MockTransport {
+	expectations: Mutex<VecDeque<Expectation>>,
+	requests: Mutex<Vec<HttpRequest>>,
+}
+
+#[derive(Debug)]
+struct Expectation {
+	url_pattern: String,
+	response: Result<HttpResponse, TransportError>,
+}
+
+impl MockTransport {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// answer the next request whose url contains `url_pattern`, e.g.
+	/// `"/videos?"` or `"id=DnJgoWDxG2A"`, with `status` and `body`
+	pub fn expect(
+		&self,
+		url_pattern: impl Into<String>,
+		status: u16,
+		body: impl Into<String>,
+	) -> &Self {
+		self.expectations.lock().unwrap().push_back(Expectation {
+			url_pattern: url_pattern.into(),
+			response: Ok(HttpResponse {
+				status,
+				body: body.into(),
+			}),
+		});
+		self
+	}
+
+	/// fail the next request whose url contains `url_pattern` with a
+	/// [`TransportError`](../transport/struct.TransportError.html), e.g. to
+	/// test the handling of connection errors
+	pub fn expect_error(
+		&self,
+		url_pattern: impl Into<String>,
+		message: impl Into<String>,
+	) -> &Self {
+		self.expectations.lock().unwrap().push_back(Expectation {
+			url_pattern: url_pattern.into(),
+			response: Err(TransportError::new(message.into())),
+		});
+		self
+	}
+
+	/// like [`expect`](#method.expect), answering with status 200 and
+	/// `response` serialized to json
+	///
+	/// # Panics
+	///
+	/// if `response` cannot be serialized
+	pub fn expect_json(&self, url_pattern: impl Into<String>, response: &impl Serialize) -> &Self {
+		let body = serde_json::to_string(response).expect("failed to serialize the response");
+		self.expect(url_pattern, 200, body)
+	}
+
+	/// the requests sent so far, including those which were not expected
+	#[must_use]
+	pub fn requests(&self) -> Vec<HttpRequest> {
+		self.requests.lock().unwrap().clone()
+	}
+
+	/// check that every expected request was sent, e.g. at the end of a test
+	///
+	/// # Panics
+	///
+	/// if an expected request was not sent
+	pub fn assert_done(&self) {
+		let expectations = self.expectations.lock().unwrap();
+		assert!(
+			expectations.is_empty(),
+			"expected requests were not sent: {:?}",
+			expectations
+				.iter()
+				.map(|expectation| &expectation.url_pattern)
+				.collect::<Vec<_>>()
+		);
+	}
+}
+
+impl Transport for MockTransport {
+	fn execute(&self, request: HttpRequest) -> TransportFuture<'_> {
+		let mut expectations = self.expectations.lock().unwrap();
+		let response = expectations
+			.iter()
+			.position(|expectation| request.url.contains(&expectation.url_pattern))
+			.and_then(|index| expectations.remove(index))
+			.map(|expectation| expectation.response)
+			.unwrap_or_else(|| {
+				Err(TransportError::new(format!(
+					"unexpected request to {}",
+					redact(&request.url)
+				)))
+			});
+		self.requests.lock().unwrap().push(request);
+		Box::pin(async { response })
+	}
+}
+
+/// [`Transport`](../transport/trait.Transport.html) recording the requests
+/// sent through another transport to a json file, or replaying the
+/// responses of such a file
+///
+/// Requests are matched by their method and url without the `key` and
+/// `access_token` parameters, which are not written to the file. Each
+/// recorded response is replayed once in the order it was recorded,
+/// requests without one fail with a connection error.
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use yt_api::{testing::{Cassette, MockTransport}, ApiKey, Client};
+/// #
+/// # futures::executor::block_on(async {
+/// let path = std::env::temp_dir().join(format!("yt-api-cassette-{}.json", std::process::id()));
+///
+/// // record, here from a mock instead of the api
+/// let mock = Arc::new(MockTransport::new());
+/// mock.expect("/videos?", 200, r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#);
+/// let recording = Arc::new(Cassette::record(&path, mock));
+/// let client = Client::with_transport(ApiKey::new("secret-key"), recording);
+/// client.videos().id("DnJgoWDxG2A").await.unwrap();
+/// assert!(!std::fs::read_to_string(&path).unwrap().contains("secret-key"));
+///
+/// // replay with another key
+/// let replaying = Arc::new(Cassette::replay(&path).unwrap());
+/// let client = Client::with_transport(ApiKey::new("other-key"), replaying);
+/// let response = client.videos().id("DnJgoWDxG2A").await.unwrap();
+/// assert_eq!(response.items[0].id, "DnJgoWDxG2A");
+/// // an unseen request fails
+/// assert!(client.videos().id("dQw4w9WgXcQ").await.is_err());
+/// # std::fs::remove_file(&path).unwrap();
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct Cassette {
+	path: PathBuf,
+	/// the transport recorded from, `None` when replaying
+	transport: Option<Arc<dyn Transport>>,
+	interactions: Mutex<Vec<Interaction>>,
+}
+
+/// a request and its response as stored in the file of a cassette
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+	method: String,
+	url: String,
+	status: u16,
+	body: String,
+	#[serde(skip)]
+	replayed: bool,
+}
+
+impl Cassette {
+	/// send the requests with `transport` and write them and their responses
+	/// to the file at `path`, replacing it
+	pub fn record(path: impl Into<PathBuf>, transport: Arc<dyn Transport>) -> Self {
+		Self {
+			path: path.into(),
+			transport: Some(transport),
+			interactions: Mutex::new(Vec::new()),
+		}
+	}
+
+	/// answer the requests with the responses recorded to the file at `path`
+	pub fn replay(path: impl AsRef<Path>) -> io::Result<Self> {
+		let path = path.as_ref();
+		let interactions = serde_json::from_str(&fs::read_to_string(path)?)
+			.map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+		Ok(Self {
+			path: path.to_path_buf(),
+			transport: None,
+			interactions: Mutex::new(interactions),
+		})
+	}
+
+	fn save(&self, interaction: Interaction) -> Result<(), TransportError> {
+		let mut interactions = self.interactions.lock().unwrap();
+		interactions.push(interaction);
+		let json = serde_json::to_string_pretty(&*interactions).map_err(TransportError::new)?;
+		fs::write(&self.path, json).map_err(|error| {
+			TransportError::new(format!(
+				"failed to write the cassette {}: {}",
+				self.path.display(),
+				error
+			))
+		})
+	}
+
+	fn play(&self, method: Method, url: &str) -> Result<HttpResponse, TransportError> {
+		let mut interactions = self.interactions.lock().unwrap();
+		let interaction = interactions
+			.iter_mut()
+			.find(|interaction| {
+				!interaction.replayed
+					&& interaction.method == method.as_str()
+					&& interaction.url == url
+			})
+			.ok_or_else(|| {
+				TransportError::new(format!(
+					"no response to {} {} was recorded in {}",
+					method,
+					url,
+					self.path.display()
+				))
+			})?;
+		interaction.replayed = true;
+		Ok(HttpResponse {
+			status: interaction.status,
+			body: interaction.body.clone(),
+		})
+	}
+}
+
+impl Transport for Cassette {
+	fn execute(&self, request: HttpRequest) -> TransportFuture<'_> {
+		let method = request.method;
+		let url = without_credentials(&request.url);
+		match &self.transport {
+			Some(transport) => Box::pin(async move {
+				let response = transport.execute(request).await?;
+				self.save(Interaction {
+					method: String::from(method.as_str()),
+					url,
+					status: response.status,
+					body: response.body.clone(),
+					replayed: false,
+				})?;
+				Ok(response)
+			}),
+			None => {
+				let response = self.play(method, &url);
+				Box::pin(async { response })
+			}
+		}
+	}
+}
+
+/// `url` without the `key` and `access_token` parameters
+fn without_credentials(url: &str) -> String {
+	match url.split_once('?') {
+		Some((path, query)) => {
+			let query = query
+				.split('&')
+				.filter(|parameter| {
+					!parameter.starts_with("key=") && !parameter.starts_with("access_token=")
+				})
+				.collect::<Vec<_>>()
+				.join("&");
+			format!("{}?{}", path, query)
+		}
+		None => url.to_string(),
+	}
+}
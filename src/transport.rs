@@ -0,0 +1,498 @@
+//! abstraction over the http client used to talk to the api
+//!
+//! By default requests are sent with [surf](https://docs.rs/surf). With the
+//! `reqwest` feature a [reqwest](https://docs.rs/reqwest) based transport is
+//! available as well, and any other client can be plugged in by implementing
+//! [`Transport`](trait.Transport.html) and passing it to
+//! [`Client::with_transport`](../struct.Client.html#method.with_transport).
+//!
+//! For `wasm32-unknown-unknown` enable the `wasm` feature without the default
+//! features, the requests are then sent by reqwest with the fetch api of the
+//! browser. There the futures of a transport do not have to be `Send`, and
+//! the api key has to allow requests from the origin of the page.
+
+use std::fmt::{self, Debug, Display};
+
+#[cfg(any(
+	feature = "curl-client",
+	all(feature = "reqwest", not(target_arch = "wasm32"))
+))]
+use super::Proxy;
+
+/// future of a [`Transport`](trait.Transport.html), which has to be `Send`
+/// except on wasm, where the futures of the browser apis are not
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = futures::future::BoxFuture<'a, Result<HttpResponse, TransportError>>;
+
+/// future of a [`Transport`](trait.Transport.html), which has to be `Send`
+/// except on wasm, where the futures of the browser apis are not
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> =
+	futures::future::LocalBoxFuture<'a, Result<HttpResponse, TransportError>>;
+
+/// http method of a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+	Get,
+	Post,
+	Put,
+	Delete,
+}
+
+impl Method {
+	/// whether sending the request twice has the same effect as sending it once
+	#[must_use]
+	pub fn is_idempotent(self) -> bool {
+		!matches!(self, Method::Post)
+	}
+
+	#[must_use]
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Method::Get => "GET",
+			Method::Post => "POST",
+			Method::Put => "PUT",
+			Method::Delete => "DELETE",
+		}
+	}
+}
+
+impl Display for Method {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// request handed to a [`Transport`](trait.Transport.html)
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+	pub method: Method,
+	pub url: String,
+	pub headers: Vec<(String, String)>,
+	pub body: Option<String>,
+}
+
+/// response returned by a [`Transport`](trait.Transport.html)
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+	pub status: u16,
+	pub body: String,
+}
+
+/// error of a [`Transport`](trait.Transport.html), e.g. when the connection failed
+#[derive(Debug, Clone)]
+pub struct TransportError {
+	message: String,
+}
+
+impl TransportError {
+	pub fn new(message: impl Display) -> Self {
+		Self {
+			message: message.to_string(),
+		}
+	}
+}
+
+impl Display for TransportError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+impl std::error::Error for TransportError {}
+
+/// http client able to execute requests against the api
+pub trait Transport: Debug + Send + Sync {
+	/// send the request and read the whole response body, regardless of the
+	/// status code
+	fn execute(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+/// [`Transport`](trait.Transport.html) using a [surf](https://docs.rs/surf)
+/// client selected by the surf feature flags
+///
+/// The client is shared by all requests of a
+/// [`Client`](../struct.Client.html) and its clones, so connections are kept
+/// alive and reused.
+///
+/// Dropping the future of a request aborts it, its connection is closed
+/// instead of reading the rest of the response.
+///
+/// With the `compression` feature responses are requested with gzip or
+/// deflate compression and decompressed before they are deserialized.
+#[cfg(feature = "surf")]
+#[derive(Debug, Clone)]
+pub struct SurfTransport {
+	client: surf::Client,
+}
+
+#[cfg(feature = "surf")]
+impl SurfTransport {
+	/// use an existing, possibly preconfigured, surf client
+	#[must_use]
+	pub fn new(client: surf::Client) -> Self {
+		Self { client }
+	}
+
+	/// a new curl based surf client sending the requests through `proxy`
+	#[cfg(feature = "curl-client")]
+	pub fn with_proxy(proxy: &Proxy) -> Result<Self, TransportError> {
+		use isahc::{
+			auth::{Authentication, Credentials},
+			config::Configurable,
+		};
+
+		let uri: isahc::http::Uri = proxy.url().parse().map_err(TransportError::new)?;
+		let mut builder = isahc::HttpClient::builder()
+			.proxy(Some(uri))
+			.proxy_blacklist(proxy.no_proxy_hosts().to_vec());
+		if let Some((username, password)) = proxy.credentials() {
+			builder = builder
+				.proxy_authentication(Authentication::basic())
+				.proxy_credentials(Credentials::new(username, password));
+		}
+		let client = builder.build().map_err(TransportError::new)?;
+		Ok(Self::new(surf::Client::with_http_client(
+			http_client::isahc::IsahcClient::from_client(client),
+		)))
+	}
+}
+
+/// a new surf client with its own connection pool
+///
+/// # Panics
+///
+/// if none of the surf client features is enabled
+#[cfg(feature = "surf")]
+impl Default for SurfTransport {
+	fn default() -> Self {
+		use std::convert::TryInto;
+
+		let client = surf::Config::new()
+			.try_into()
+			.expect("failed to create the surf client");
+		Self { client }
+	}
+}
+
+#[cfg(feature = "surf")]
+impl Transport for SurfTransport {
+	fn execute(&self, request: HttpRequest) -> TransportFuture<'_> {
+		Box::pin(async move {
+			let method = match request.method {
+				Method::Get => surf::http::Method::Get,
+				Method::Post => surf::http::Method::Post,
+				Method::Put => surf::http::Method::Put,
+				Method::Delete => surf::http::Method::Delete,
+			};
+			let url = surf::Url::parse(&request.url).map_err(TransportError::new)?;
+			let mut builder = surf::RequestBuilder::new(method, url);
+			for (name, value) in &request.headers {
+				builder = builder.header(name.as_str(), value.as_str());
+			}
+			if let Some(body) = request.body {
+				builder = builder.body(body);
+			}
+			#[cfg(feature = "compression")]
+			{
+				builder = builder.header("Accept-Encoding", "gzip, deflate");
+			}
+			let mut response = self
+				.client
+				.send(builder)
+				.await
+				.map_err(TransportError::new)?;
+			#[cfg(not(feature = "compression"))]
+			let body = response.body_string().await.map_err(TransportError::new)?;
+			#[cfg(feature = "compression")]
+			let body = {
+				let encoding = response
+					.header("Content-Encoding")
+					.map(|values| values.last().as_str().to_string());
+				let body = response.body_bytes().await.map_err(TransportError::new)?;
+				decompress(encoding.as_deref(), body)?
+			};
+			Ok(HttpResponse {
+				status: response.status().into(),
+				body,
+			})
+		})
+	}
+}
+
+/// decompresses a body sent with the `Content-Encoding` `encoding`
+///
+/// Bodies which do not look compressed are passed through, e.g. when the
+/// http client already decompressed them.
+#[cfg(all(feature = "surf", feature = "compression"))]
+fn decompress(encoding: Option<&str>, body: Vec<u8>) -> Result<String, TransportError> {
+	use std::io::Read;
+
+	use flate2::read::{GzDecoder, ZlibDecoder};
+
+	let is_gzip = body.starts_with(&[0x1f, 0x8b]);
+	let is_zlib =
+		body.len() >= 2 && body[0] & 0x0f == 8 && u16::from_be_bytes([body[0], body[1]]) % 31 == 0;
+	let mut decompressed = String::new();
+	match encoding.map(str::trim) {
+		Some(encoding) if encoding.eq_ignore_ascii_case("gzip") && is_gzip => {
+			GzDecoder::new(&body[..]).read_to_string(&mut decompressed)
+		}
+		Some(encoding) if encoding.eq_ignore_ascii_case("deflate") && is_zlib => {
+			ZlibDecoder::new(&body[..]).read_to_string(&mut decompressed)
+		}
+		_ => return String::from_utf8(body).map_err(TransportError::new),
+	}
+	.map_err(TransportError::new)?;
+	Ok(decompressed)
+}
+
+/// [`Transport`](trait.Transport.html) using a
+/// [reqwest](https://docs.rs/reqwest) client
+///
+/// Outside of the browser the requests of reqwest need a tokio 1.x runtime
+/// with its io and time drivers, e.g. of `#[tokio::main]`. Awaiting them in
+/// another executor like `futures::executor::block_on` or async-std panics
+/// with "there is no reactor running". The [blocking
+/// client](../blocking/index.html) runs them on a runtime of its own.
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+	client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+	/// use an existing, possibly preconfigured, reqwest client
+	#[must_use]
+	pub fn new(client: reqwest::Client) -> Self {
+		Self { client }
+	}
+
+	/// a new reqwest client sending the requests through `proxy`, socks
+	/// proxies need the `socks` feature
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn with_proxy(proxy: &Proxy) -> Result<Self, TransportError> {
+		let mut reqwest_proxy = reqwest::Proxy::all(proxy.url()).map_err(TransportError::new)?;
+		if let Some((username, password)) = proxy.credentials() {
+			reqwest_proxy = reqwest_proxy.basic_auth(username, password);
+		}
+		let reqwest_proxy = reqwest_proxy.no_proxy(reqwest::NoProxy::from_string(
+			&proxy.no_proxy_hosts().join(","),
+		));
+		let client = reqwest::Client::builder()
+			.proxy(reqwest_proxy)
+			.build()
+			.map_err(TransportError::new)?;
+		Ok(Self::new(client))
+	}
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for ReqwestTransport {
+	fn execute(&self, request: HttpRequest) -> TransportFuture<'_> {
+		Box::pin(async move {
+			let method = match request.method {
+				Method::Get => reqwest::Method::GET,
+				Method::Post => reqwest::Method::POST,
+				Method::Put => reqwest::Method::PUT,
+				Method::Delete => reqwest::Method::DELETE,
+			};
+			let mut builder = self.client.request(method, &request.url);
+			for (name, value) in &request.headers {
+				builder = builder.header(name.as_str(), value.as_str());
+			}
+			if let Some(body) = request.body {
+				builder = builder.body(body);
+			}
+			let response = builder.send().await.map_err(TransportError::new)?;
+			let status = response.status().as_u16();
+			let body = response.text().await.map_err(TransportError::new)?;
+			Ok(HttpResponse { status, body })
+		})
+	}
+}
+
+/// transport used by [`Client::new`](../struct.Client.html#method.new),
+/// sending the requests through the proxy of the environment if there is one
+#[cfg(any(feature = "surf", feature = "reqwest"))]
+pub(crate) fn default_transport() -> std::sync::Arc<dyn Transport> {
+	#[cfg(any(
+		feature = "curl-client",
+		all(feature = "reqwest", not(target_arch = "wasm32"))
+	))]
+	{
+		if let Some(proxy) = Proxy::from_env() {
+			match proxy_transport(&proxy) {
+				Ok(transport) => return transport,
+				Err(error) => log::warn!("ignoring the proxy of the environment: {}", error),
+			}
+		}
+	}
+	#[cfg(feature = "surf")]
+	return std::sync::Arc::new(SurfTransport::default());
+	#[cfg(not(feature = "surf"))]
+	return std::sync::Arc::new(ReqwestTransport::default());
+}
+
+/// transport used by [`Client::proxy`](../struct.Client.html#method.proxy),
+/// curl supports proxies while the other surf clients do not
+#[cfg(feature = "curl-client")]
+pub(crate) fn proxy_transport(
+	proxy: &Proxy,
+) -> Result<std::sync::Arc<dyn Transport>, TransportError> {
+	Ok(std::sync::Arc::new(SurfTransport::with_proxy(proxy)?))
+}
+
+/// transport used by [`Client::proxy`](../struct.Client.html#method.proxy)
+#[cfg(all(
+	feature = "reqwest",
+	not(feature = "curl-client"),
+	not(target_arch = "wasm32")
+))]
+pub(crate) fn proxy_transport(
+	proxy: &Proxy,
+) -> Result<std::sync::Arc<dyn Transport>, TransportError> {
+	Ok(std::sync::Arc::new(ReqwestTransport::with_proxy(proxy)?))
+}
+
+#[cfg(all(test, feature = "surf"))]
+mod tests {
+	use std::{
+		io::{BufRead, BufReader, Read, Write},
+		net::TcpListener,
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			mpsc, Arc,
+		},
+		thread,
+		time::Duration,
+	};
+
+	use futures::future::{select, Either};
+	use futures_timer::Delay;
+
+	use crate::{ApiKey, Client};
+
+	fn client(listener: &TcpListener) -> Client {
+		let address = listener.local_addr().unwrap();
+		Client::new(ApiKey::new("key")).base_url(format!("http://{}", address))
+	}
+
+	#[test]
+	fn connections_are_reused() {
+		// a mock server counting the connections it accepts
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let client = client(&listener);
+		let connections = Arc::new(AtomicUsize::new(0));
+		let accepted = connections.clone();
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				accepted.fetch_add(1, Ordering::SeqCst);
+				let mut stream = BufReader::new(stream.unwrap());
+				thread::spawn(move || loop {
+					let mut line = String::new();
+					while line != "\r\n" {
+						line.clear();
+						if stream.read_line(&mut line).unwrap_or(0) == 0 {
+							return;
+						}
+					}
+					let body = r#"{ "items": [] }"#;
+					let response = format!(
+						"HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+						body.len(),
+						body
+					);
+					stream.get_mut().write_all(response.as_bytes()).unwrap();
+				});
+			}
+		});
+
+		futures::executor::block_on(async {
+			client.videos().id("DnJgoWDxG2A").await.unwrap();
+			client.clone().videos().id("dQw4w9WgXcQ").await.unwrap();
+		});
+		assert_eq!(connections.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn dropped_request_is_aborted() {
+		// a mock server which only starts to respond once the request was
+		// dropped, with a body far larger than the socket buffers
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let client = client(&listener);
+		let (dropped, on_drop) = mpsc::channel();
+		let server = thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			assert!(stream.read(&mut [0; 4096]).unwrap() > 0);
+			on_drop.recv().unwrap();
+			let chunk = [b' '; 64 * 1024];
+			write!(
+				stream,
+				"HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n",
+				1024 * chunk.len()
+			)
+			.unwrap();
+			(0..1024)
+				.map(|_| stream.write_all(&chunk))
+				.any(|result| result.is_err())
+		});
+
+		futures::executor::block_on(async {
+			let request = client.videos().id("DnJgoWDxG2A").send();
+			match select(request, Delay::new(Duration::from_millis(200))).await {
+				Either::Left(_) => panic!("the server does not respond yet"),
+				Either::Right((_, request)) => drop(request),
+			}
+		});
+		dropped.send(()).unwrap();
+		assert!(server.join().unwrap(), "the whole response was read");
+	}
+
+	#[cfg(feature = "compression")]
+	#[test]
+	fn compressed_responses_are_decompressed() {
+		use flate2::{write::GzEncoder, Compression};
+
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let client = client(&listener);
+		let server = thread::spawn(move || {
+			let body = r#"{ "items": [{ "id": "DnJgoWDxG2A", "snippet": { "title": "Grüße" } }] }"#;
+			let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+			gzip.write_all(body.as_bytes()).unwrap();
+			let compressed = gzip.finish().unwrap();
+			let responses = vec![(Some("gzip"), compressed), (None, body.as_bytes().to_vec())];
+			let mut requests = Vec::new();
+			for (encoding, body) in responses {
+				let (mut stream, _) = listener.accept().unwrap();
+				let mut request = [0; 4096];
+				let length = stream.read(&mut request).unwrap();
+				requests.push(String::from_utf8_lossy(&request[..length]).to_lowercase());
+				let encoding = encoding.map_or(String::new(), |encoding| {
+					format!("content-encoding: {}\r\n", encoding)
+				});
+				write!(
+					stream,
+					"HTTP/1.1 200 OK\r\ncontent-type: application/json; charset=UTF-8\r\n{}content-length: {}\r\nconnection: close\r\n\r\n",
+					encoding,
+					body.len(),
+				)
+				.unwrap();
+				stream.write_all(&body).unwrap();
+			}
+			requests
+		});
+
+		futures::executor::block_on(async {
+			for _ in 0..2 {
+				let response = client.videos().id("DnJgoWDxG2A").await.unwrap();
+				assert_eq!(response.items[0].snippet.title.as_deref(), Some("Grüße"));
+			}
+		});
+		let requests = server.join().unwrap();
+		assert!(requests[0]
+			.lines()
+			.any(|line| line.starts_with("accept-encoding:") && line.contains("gzip")));
+	}
+}
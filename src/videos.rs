@@ -1,277 +1,1477 @@
 use std::{
-	future::Future,
-	pin::Pin,
-	task::{Context, Poll},
+	collections::{HashMap, HashSet},
+	fmt::{self, Display},
 };
 
-use chrono::{DateTime, Utc};
-use futures::future::BoxFuture;
-use log::debug;
-use serde::{Deserialize, Serialize, Serializer};
-use snafu::{ResultExt, Snafu};
-
-use super::ApiKey;
-
-/// custom error type for the search endpoint
-#[derive(Debug, Snafu)]
-pub enum Error {
-	#[snafu(display("failed to connect to the api: {}", string))]
-	Connection { string: String },
-	#[snafu(display("failed to deserialize: {} {}", string, source))]
-	Deserialization {
-		string: String,
-		source: serde_json::Error,
-	},
-	#[snafu(display("failed to serialize: {}", source))]
-	Serialization {
-		source: serde_urlencoded::ser::Error,
-	},
-}
-
-impl From<surf::Error> for Error {
-	fn from(surf_error: surf::Error) -> Self {
-		Error::Connection {
-			string: surf_error.to_string(),
-		}
-	}
-}
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+pub use super::common::{PageInfo, Thumbnail, Thumbnails};
+#[cfg(feature = "chrono")]
+use super::published::Published;
+#[allow(deprecated)]
+pub use super::search::Order;
+pub use super::search::{
+	ChannelType, EventType, ItemType, SafeSearch, SearchOrder, VideoCaption, VideoDefinition,
+	VideoDimension, VideoDuration, VideoLicense, VideoLocation, VideoType,
+};
+pub use super::Error;
+use super::{
+	common,
+	error::{InvalidParameter, InvalidRequest, MissingAccessToken, Serialization},
+	ids, limits,
+	pagination::{self, Paginated},
+	request::{self, Request, ResponseFuture},
+	AccessToken, ApiKey, ChannelId, Client, LanguageCode, Localized, RegionCode, Timestamp,
+	VideoId,
+};
 
 /// request struct for the search endpoint
+///
+/// [`send_raw`](#method.send_raw) keeps the json of the response, e.g. to
+/// read parts this crate does not model yet:
+///
+/// ```rust,no_run
+/// # use yt_api::{ApiKey, Client};
+/// # futures::executor::block_on(async {
+/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+/// let (response, json) = client.videos().id("DnJgoWDxG2A").send_raw().await.unwrap();
+/// println!("{:?}", json["items"][0]["newPart"]);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
 pub struct Videos {
-	future: Option<BoxFuture<'static, Result<Response, Error>>>,
-	data: Option<VideosData>,
+	data: VideosData,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct VideosData {
+	#[serde(skip)]
+	client: Client,
 	key: ApiKey,
 	part: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	id: Option<String>,
+	fields: Option<String>,
+	#[serde(skip)]
+	id: Vec<VideoId>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	chart: Option<&'static str>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	region_code: Option<RegionCode>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	video_category_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	max_results: Option<u8>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	page_token: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	hl: Option<LanguageCode>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	on_behalf_of_content_owner: Option<String>,
 }
 
 impl Videos {
-	const URL: &'static str = "https://www.googleapis.com/youtube/v3/videos";
+	const QUOTA_COST: u32 = 1;
+	const PATH: &'static str = "videos";
+
+	/// maximum number of ids the api accepts in one request
+	pub const MAX_IDS: usize = 50;
+
+	/// maximum number of videos of a chart the api returns on one page
+	pub const MAX_RESULTS: u8 = 50;
 
 	/// create struct with an [`ApiKey`](../struct.ApiKey.html)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
 	#[must_use]
 	pub fn new(key: ApiKey) -> Self {
+		Client::new(key).videos()
+	}
+
+	/// look up a single video, see [`single`](#method.single)
+	#[cfg(any(feature = "surf", feature = "reqwest"))]
+	pub fn get_one(key: ApiKey, id: impl Into<VideoId>) -> ResponseFuture<Option<VideoResult>> {
+		Self::new(key).id(id).single()
+	}
+
+	pub(crate) fn with_client(client: Client) -> Self {
 		Self {
-			future: None,
-			data: Some(VideosData {
-				key,
+			data: VideosData {
+				key: client.key.clone(),
+				client,
 				part: String::from("snippet,contentDetails"),
-				id: None
-			}),
+				fields: None,
+				id: Vec::new(),
+				chart: None,
+				region_code: None,
+				video_category_id: None,
+				max_results: None,
+				page_token: None,
+				hl: None,
+				on_behalf_of_content_owner: None,
+			},
 		}
 	}
 
+	/// the video to look up, urls of videos are accepted as well
+	///
+	/// Replaces the chart selected with [`most_popular`](#method.most_popular).
 	#[must_use]
-	pub fn id(mut self, id: &str) -> Self {
-		let mut data = self.data.take().unwrap();
-		data.id = Some(id.into());
-		self.data = Some(data);
+	pub fn id(mut self, id: impl Into<VideoId>) -> Self {
+		self.data.id = vec![id.into()];
+		self.data.chart = None;
 		self
 	}
 
-}
+	/// look up multiple videos at once, at most
+	/// [`MAX_IDS`](#associatedconstant.MAX_IDS) per request
+	///
+	/// The ids are sent as one comma separated `id` parameter:
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Request};
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let request = client.videos().ids(vec!["DnJgoWDxG2A", "dQw4w9WgXcQ"]);
+	/// assert!(request.build_url().unwrap().ends_with("&id=DnJgoWDxG2A,dQw4w9WgXcQ"));
+	/// ```
+	#[must_use]
+	pub fn ids<I>(mut self, ids: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<VideoId>,
+	{
+		self.data.id = ids.into_iter().map(Into::into).collect();
+		self.data.chart = None;
+		self
+	}
+
+	/// look up the most popular videos instead of videos by their
+	/// [`id`](#method.id), replacing the ids set before
+	///
+	/// The chart can be narrowed down to a [`region_code`](#method.region_code)
+	/// and a [`video_category_id`](#method.video_category_id). The api only
+	/// knows the categories of a region, so a category requires a region code
+	/// as well. Like every videos request each page of the chart costs
+	/// [`quota_cost`](#method.quota_cost) units.
+	///
+	/// ```rust
+	/// # use yt_api::{ApiKey, Client, Error, Request};
+	/// let client = Client::new(ApiKey::new("key"));
+	///
+	/// let trending = client.videos().id("DnJgoWDxG2A").most_popular().region_code("de");
+	/// let url = trending.build_url().unwrap();
+	/// assert!(url.contains("&chart=mostPopular&regionCode=DE"));
+	/// assert!(!url.contains("&id="));
+	///
+	/// let music = client.videos().most_popular().region_code("US").video_category_id("10");
+	/// assert!(music.build_url().unwrap().contains("&chart=mostPopular&regionCode=US&videoCategoryId=10"));
+	///
+	/// let without_region = client.videos().most_popular().video_category_id("10");
+	/// assert!(matches!(
+	///     without_region.build_url(),
+	///     Err(Error::InvalidParameter { field, .. }) if field == "regionCode"
+	/// ));
+	/// let without_chart = client.videos().id("DnJgoWDxG2A").region_code("US");
+	/// assert!(matches!(without_chart.build_url(), Err(Error::InvalidRequest { .. })));
+	/// let paged_ids = client.videos().id("DnJgoWDxG2A").max_results(5u8);
+	/// assert!(matches!(paged_ids.build_url(), Err(Error::InvalidRequest { .. })));
+	/// // either ids or a chart have to be requested
+	/// assert!(matches!(
+	///     client.videos().build_url(),
+	///     Err(Error::InvalidParameter { field, .. }) if field == "filter"
+	/// ));
+	/// ```
+	#[must_use]
+	pub fn most_popular(mut self) -> Self {
+		self.data.chart = Some("mostPopular");
+		self.data.id = Vec::new();
+		self
+	}
+
+	/// region of the [`most_popular`](#method.most_popular) chart
+	#[must_use]
+	pub fn region_code(mut self, region_code: impl Into<RegionCode>) -> Self {
+		self.data.region_code = Some(region_code.into());
+		self
+	}
 
-impl Future for Videos {
-	type Output = Result<Response, Error>;
+	/// category of the [`most_popular`](#method.most_popular) chart, which
+	/// requires a [`region_code`](#method.region_code)
+	#[must_use]
+	pub fn video_category_id(mut self, video_category_id: impl Into<String>) -> Self {
+		self.data.video_category_id = Some(video_category_id.into());
+		self
+	}
 
-	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-		if self.future.is_none() {
-			let data = self.data.take().unwrap();
-			self.future = Some(Box::pin(async move {
-				let url = format!(
-					"{}?{}",
-					Self::URL,
-					serde_urlencoded::to_string(&data).context(Serialization)?
-				);
-				debug!("getting {}", url);
-				let response = surf::get(&url).recv_string().await?;
-				serde_json::from_str(&response)
-					.with_context(move || Deserialization { string: response })
-			}));
+	/// number of videos of the [`most_popular`](#method.most_popular) chart
+	/// per page, at most [`MAX_RESULTS`](#associatedconstant.MAX_RESULTS)
+	#[must_use]
+	pub fn max_results(mut self, max_results: impl Into<u8>) -> Self {
+		self.data.max_results = Some(max_results.into());
+		self
+	}
+
+	/// page of the [`most_popular`](#method.most_popular) chart
+	#[must_use]
+	pub fn page_token(mut self, page_token: impl Into<String>) -> Self {
+		self.data.page_token = Some(page_token.into());
+		self
+	}
+
+	/// fetch pages until `n` videos were received or the last page was
+	/// reached, without requesting pages which are not needed
+	///
+	/// The [`max_results`](#method.max_results) of the request are asked for
+	/// per page, at most `n` of the chart if they are not set.
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let trending = client.videos().most_popular().region_code("de").collect_n(25).await.unwrap();
+	/// # });
+	/// ```
+	pub async fn collect_n(self, n: usize) -> Result<Vec<VideoResult>, Error> {
+		if n == 0 {
+			return Ok(Vec::new());
 		}
+		let request = match (self.data.chart, self.data.max_results) {
+			(Some(_), None) => {
+				let max_results = n.min(usize::from(Self::MAX_RESULTS)) as u8;
+				self.max_results(max_results)
+			}
+			_ => self,
+		};
+		request.pages().items().take(n).try_collect().await
+	}
 
-		self.future.as_mut().unwrap().as_mut().poll(cx)
+	/// look up any number of videos, splitting them into requests of
+	/// [`MAX_IDS`](#associatedconstant.MAX_IDS) ids with all other parameters
+	/// taken from this request, of which at most `concurrency` are sent at
+	/// the same time
+	///
+	/// The items of all successful requests are collected in no particular
+	/// order, a failed request does not abort the others.
+	pub async fn fetch_all<I>(self, ids: I, concurrency: usize) -> FetchAllResult
+	where
+		I: IntoIterator,
+		I::Item: Into<VideoId>,
+	{
+		let mut batches = self.batches(ids, concurrency);
+		let mut result = FetchAllResult::default();
+		while let Some(batch) = batches.next().await {
+			match batch.result {
+				Ok(items) => result.items.extend(items),
+				Err(error) => result.errors.push(error),
+			}
+		}
+		result
 	}
-}
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ChannelType {
-	Any,
-	Show,
+	/// like [`fetch_all`](#method.fetch_all), but streams the result of each
+	/// request as soon as it arrives, together with the ids it asked for
+	///
+	/// ```rust,no_run
+	/// # use futures::StreamExt;
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// # let ids = Vec::<String>::new();
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let mut batches = Box::pin(client.videos().batches(ids, 2));
+	/// while let Some(batch) = batches.next().await {
+	///     println!("not found: {:?}", batch.missing_ids());
+	/// }
+	/// # });
+	/// ```
+	pub fn batches<I>(self, ids: I, concurrency: usize) -> impl Stream<Item = Batch>
+	where
+		I: IntoIterator,
+		I::Item: Into<VideoId>,
+	{
+		let ids = ids.into_iter().map(Into::into).collect::<Vec<VideoId>>();
+		let requests = ids
+			.chunks(Self::MAX_IDS)
+			.map(|chunk| {
+				let ids = chunk.to_vec();
+				let response = self.clone().ids(ids.clone()).send();
+				async move {
+					Batch {
+						ids,
+						result: response.await.map(|response| response.items),
+					}
+				}
+			})
+			.collect::<Vec<_>>();
+		stream::iter(requests).buffer_unordered(concurrency.max(1))
+	}
+
+	/// return the [`localized`](struct.Snippet.html#structfield.localized)
+	/// title and description of the snippet in this language, if a
+	/// translation exists
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let response = client.videos().id("DnJgoWDxG2A").hl("de").await.unwrap();
+	/// if let Some(localized) = &response.items[0].snippet.localized {
+	///     println!("{:?}", localized.title);
+	/// }
+	/// # });
+	/// ```
+	#[must_use]
+	pub fn hl(mut self, hl: impl Into<LanguageCode>) -> Self {
+		self.data.hl = Some(hl.into());
+		self
+	}
+
+	/// act on behalf of the content owner with the given id, only usable by
+	/// youtube content partners authorized with an
+	/// [`access_token`](#method.access_token) of a cms account linked to it
+	#[must_use]
+	pub fn on_behalf_of_content_owner(
+		mut self,
+		on_behalf_of_content_owner: impl Into<String>,
+	) -> Self {
+		self.data.on_behalf_of_content_owner = Some(on_behalf_of_content_owner.into());
+		self
+	}
+
+	/// authorize the request with an [`AccessToken`](../struct.AccessToken.html)
+	#[must_use]
+	pub fn access_token(mut self, access_token: AccessToken) -> Self {
+		self.data.client.access_token = Some(access_token);
+		self
+	}
+
+	/// request the `statistics` part with the view, like and comment counts
+	#[must_use]
+	pub fn statistics(mut self) -> Self {
+		self.data.part.push_str(",statistics");
+		self
+	}
+
+	/// request the `localizations` part with the translations of the title
+	/// and description into every language
+	#[must_use]
+	pub fn localizations(mut self) -> Self {
+		self.data.part.push_str(",localizations");
+		self
+	}
+
+	/// request the `fileDetails` part, only available to the video owner
+	/// and therefore requires an [`access_token`](#method.access_token)
+	#[must_use]
+	pub fn file_details(mut self) -> Self {
+		self.data.part.push_str(",fileDetails");
+		self
+	}
+
+	/// request the `processingDetails` part, only available to the video owner
+	/// and therefore requires an [`access_token`](#method.access_token)
+	#[must_use]
+	pub fn processing_details(mut self) -> Self {
+		self.data.part.push_str(",processingDetails");
+		self
+	}
+
+	/// request the `suggestions` part, only available to the video owner
+	/// and therefore requires an [`access_token`](#method.access_token)
+	#[must_use]
+	pub fn suggestions(mut self) -> Self {
+		self.data.part.push_str(",suggestions");
+		self
+	}
+
+	/// only return the selected fields of the response, e.g.
+	/// `items(id,snippet/title),nextPageToken`, fields which are not selected
+	/// are left at their default value
+	///
+	/// ```rust
+	/// # use yt_api::videos::Response;
+	/// // response to `fields=items(id,snippet/title)`
+	/// let response: Response = serde_json::from_str(r#"{
+	///     "items": [{ "id": "DnJgoWDxG2A", "snippet": { "title": "title" } }]
+	/// }"#).unwrap();
+	///
+	/// assert_eq!(response.items[0].snippet.title.as_deref(), Some("title"));
+	/// ```
+	#[must_use]
+	pub fn fields(mut self, fields: impl Into<String>) -> Self {
+		self.data.fields = Some(fields.into());
+		self
+	}
+
+	/// send the request for a single video, resolving to `None` if the video
+	/// does not exist or is private instead of to an empty response
+	///
+	/// Fails with [`Error::InvalidParameter`](../enum.Error.html#variant.InvalidParameter)
+	/// if more than one video is returned, e.g. because multiple
+	/// [`ids`](#method.ids) were requested.
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// match client.videos().id("DnJgoWDxG2A").single().await.unwrap() {
+	///     Some(video) => println!("{:?}", video.snippet.title),
+	///     None => println!("the video was deleted or is private"),
+	/// }
+	/// # });
+	/// ```
+	pub fn single(self) -> ResponseFuture<Option<VideoResult>> {
+		let response = self.send();
+		ResponseFuture::new("Videos", async move {
+			let mut items = response.await?.items;
+			if items.len() > 1 {
+				return InvalidParameter {
+					field: "id",
+					message: format!("{} videos were returned instead of one", items.len()),
+				}
+				.fail();
+			}
+			Ok(items.pop())
+		})
+	}
+
+	/// send the request for multiple [`ids`](#method.ids) and report which
+	/// of them were not found
+	///
+	/// Malformed ids are left out of the request instead of failing all of
+	/// it, and the requested ids for which the api returned no video, e.g.
+	/// because they were deleted or are private, are collected in `missing`.
+	///
+	/// ```rust,no_run
+	/// # use yt_api::{ApiKey, Client};
+	/// # futures::executor::block_on(async {
+	/// let client = Client::new(ApiKey::new("your-youtube-api-key"));
+	/// let batch = client.videos().ids(vec!["DnJgoWDxG2A", "dQw4w9WgXcQ", "typo"]).send_batch().await.unwrap();
+	/// println!("not found: {:?}, malformed: {:?}", batch.missing, batch.malformed);
+	/// # });
+	/// ```
+	pub fn send_batch(mut self) -> ResponseFuture<BatchResponse> {
+		let (requested, malformed): (Vec<_>, Vec<_>) = self
+			.data
+			.id
+			.drain(..)
+			.partition(|id| VideoId::is_valid(id.as_str()));
+		self.data.id = requested.clone();
+		ResponseFuture::new("Videos", async move {
+			if requested.is_empty() && !malformed.is_empty() {
+				return Ok(BatchResponse {
+					malformed,
+					..BatchResponse::default()
+				});
+			}
+			let found = self.send().await?.items;
+			let returned = found.iter().map(|item| &item.id).collect::<HashSet<_>>();
+			let missing = requested
+				.iter()
+				.filter(|id| !returned.contains(id))
+				.cloned()
+				.collect();
+			Ok(BatchResponse {
+				found,
+				missing,
+				malformed,
+			})
+		})
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum EventType {
-	Completed,
-	Live,
-	Upcoming,
+/// look up any number of videos, see [`Videos::fetch_all`](struct.Videos.html#method.fetch_all)
+#[cfg(any(feature = "surf", feature = "reqwest"))]
+pub async fn fetch_all<I>(key: ApiKey, ids: I, concurrency: usize) -> FetchAllResult
+where
+	I: IntoIterator,
+	I::Item: Into<VideoId>,
+{
+	Videos::new(key).fetch_all(ids, concurrency).await
 }
 
-#[derive(Debug, Clone)]
-pub struct VideoLocation {
-	longitude: f32,
-	latitude: f32,
+/// result of one request of [`Videos::batches`](struct.Videos.html#method.batches)
+#[derive(Debug)]
+pub struct Batch {
+	/// the ids the request asked for
+	pub ids: Vec<VideoId>,
+	/// the videos found, deleted and private videos are left out by the api
+	pub result: Result<Vec<VideoResult>, Error>,
 }
 
-impl VideoLocation {
+impl Batch {
+	/// the ids for which no video was returned, all of them if the request
+	/// failed
 	#[must_use]
-	pub fn new(longitude: f32, latitude: f32) -> Self {
-		Self {
-			longitude,
-			latitude,
-		}
+	pub fn missing_ids(&self) -> Vec<&VideoId> {
+		let found = match &self.result {
+			Ok(items) => items.iter().map(|item| &item.id).collect(),
+			Err(_) => HashSet::new(),
+		};
+		self.ids.iter().filter(|id| !found.contains(id)).collect()
 	}
 }
 
-impl Serialize for VideoLocation {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: Serializer,
-	{
-		serializer.serialize_str(&format!("{},{}", self.longitude, self.latitude))
-	}
+/// videos looked up with [`Videos::send_batch`](struct.Videos.html#method.send_batch)
+#[derive(Debug, Clone, Default)]
+pub struct BatchResponse {
+	/// the videos returned by the api
+	pub found: Vec<VideoResult>,
+	/// the requested ids for which no video was returned
+	pub missing: Vec<VideoId>,
+	/// the ids which are no video ids and were not requested
+	pub malformed: Vec<VideoId>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum Order {
-	Date,
-	Rating,
-	Relevance,
-	Title,
-	VideoCount,
-	ViewCount,
+/// url of the watch page of a video, urls of videos are accepted as well
+///
+/// ```rust
+/// use yt_api::videos::{embed_url, short_url, watch_url};
+///
+/// assert_eq!(watch_url("DnJgoWDxG2A"), "https://www.youtube.com/watch?v=DnJgoWDxG2A");
+/// assert_eq!(embed_url("DnJgoWDxG2A"), "https://www.youtube.com/embed/DnJgoWDxG2A");
+/// assert_eq!(short_url("https://www.youtube.com/shorts/DnJgoWDxG2A"), "https://youtu.be/DnJgoWDxG2A");
+/// ```
+#[must_use]
+pub fn watch_url(id: impl Into<VideoId>) -> String {
+	id.into().watch_url()
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum SafeSearch {
-	Moderate,
-	Strict,
+/// url of the player for embedding a video into a webpage
+#[must_use]
+pub fn embed_url(id: impl Into<VideoId>) -> String {
+	id.into().embed_url()
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum ItemType {
-	Channel,
-	Playlist,
-	Video,
+/// shortened `youtu.be` url for sharing a video
+#[must_use]
+pub fn short_url(id: impl Into<VideoId>) -> String {
+	id.into().short_url()
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoCaption {
-	ClosedCaption,
-	None,
+/// ids of the videos linked in `text`, e.g. a description or a chat
+/// message, in the order they first appear
+///
+/// All urls recognized by [`VideoId::from_url`](../struct.VideoId.html#method.from_url)
+/// are found, also when they are wrapped in brackets, quotes or markdown or
+/// followed by punctuation. Bare ids are not taken from the text.
+///
+/// ```rust
+/// # use yt_api::videos::extract_video_ids;
+/// let text = "Intro: https://www.youtube.com/watch?v=DnJgoWDxG2A&t=30, the short \
+///     (youtu.be/dQw4w9WgXcQ) and [the clip](https://youtube.com/shorts/kJQP7kiw5Fk).
+///     Embedded as <iframe src=\"https://www.youtube.com/embed/9bZkp7q19f0\">.
+///     Again https://youtu.be/DnJgoWDxG2A! Not a video: https://www.youtube.com/@GoogleDevelopers,
+///     https://example.com/watch?v=jNQXAC9IVRw or just jNQXAC9IVRw";
+///
+/// let ids = extract_video_ids(text);
+/// assert_eq!(ids, ["DnJgoWDxG2A", "dQw4w9WgXcQ", "kJQP7kiw5Fk", "9bZkp7q19f0"]);
+/// ```
+#[must_use]
+pub fn extract_video_ids(text: &str) -> Vec<VideoId> {
+	let mut seen = HashSet::new();
+	text.split(|c: char| c.is_whitespace() || "\"'<>()[]{}|".contains(c))
+		.filter_map(|word| {
+			// the url might be glued to the text before it, like `link:https://…`
+			let start = word.find("://").map_or(0, |separator| {
+				word[..separator]
+					.rfind(|c: char| !c.is_ascii_alphabetic())
+					.map_or(0, |before| before + 1)
+			});
+			let url = word[start..].trim_end_matches(|c: char| ".,;:!?*_".contains(c));
+			VideoId::from_url(url).ok()
+		})
+		.filter(|id| seen.insert(id.clone()))
+		.collect()
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoDefinition {
-	High,
-	Standard,
+/// videos found by [`fetch_all`](fn.fetch_all.html) and the errors of the
+/// requests which failed
+#[derive(Debug, Default)]
+pub struct FetchAllResult {
+	pub items: Vec<VideoResult>,
+	pub errors: Vec<Error>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub enum VideoDimension {
-	#[serde(rename = "3d")]
-	Three,
-	#[serde(rename = "2d")]
-	Two,
-}
+impl VideosData {
+	const OWNER_PARTS: &'static [&'static str] =
+		&["fileDetails", "processingDetails", "suggestions"];
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoDuration {
-	Long,
-	Medium,
-	Short,
+	fn check_authorization(&self) -> Result<(), Error> {
+		if self.client.access_token.is_some() {
+			return Ok(());
+		}
+		match self
+			.part
+			.split(',')
+			.find(|part| Self::OWNER_PARTS.contains(part))
+		{
+			Some(part) => MissingAccessToken {
+				required_by: format!("the {} part", part),
+			}
+			.fail(),
+			None => Ok(()),
+		}
+	}
+
+	fn check_chart(&self) -> Result<(), Error> {
+		request::exactly_one_filter(&[
+			("id", !self.id.is_empty()),
+			("chart", self.chart.is_some()),
+		])?;
+		if let Some(region_code) = &self.region_code {
+			region_code.validate()?;
+		}
+		let chart_filters = [
+			("regionCode", self.region_code.is_some()),
+			("videoCategoryId", self.video_category_id.is_some()),
+			("maxResults", self.max_results.is_some()),
+			("pageToken", self.page_token.is_some()),
+		];
+		if self.chart.is_none() {
+			if let Some((filter, _)) = chart_filters.iter().find(|(_, set)| *set) {
+				return InvalidRequest {
+					reason: format!("{} requires chart=mostPopular", filter),
+				}
+				.fail();
+			}
+		} else if self.video_category_id.is_some() && self.region_code.is_none() {
+			return InvalidParameter {
+				field: "regionCode",
+				message: "is required for the chart of a videoCategoryId",
+			}
+			.fail();
+		}
+		Ok(())
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoLicense {
-	CreativeCommon,
-	Youtube,
+impl Request for Videos {
+	fn build_url(&self) -> Result<String, Error> {
+		let data = &self.data;
+		data.check_authorization()?;
+		data.check_chart()?;
+		if let Some(hl) = &data.hl {
+			hl.validate("hl")?;
+		}
+		limits::check(
+			"maxResults",
+			&limits::VIDEOS_MAX_RESULTS,
+			data.max_results.map(u16::from),
+		)?;
+		let mut query = serde_urlencoded::to_string(data).context(Serialization)?;
+		ids::append_comma_separated(&mut query, "id", &data.id);
+		Ok(data.client.url(Self::PATH, &query))
+	}
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub enum VideoType {
-	Episode,
-	Movie,
+request::request_methods!(Videos => Response, "youtube#videoListResponse");
+
+impl Paginated for Videos {
+	type Response = Response;
+
+	fn with_page_token(&self, page_token: Option<&str>) -> Self {
+		let mut request = self.clone();
+		if let Some(page_token) = page_token {
+			request = request.page_token(page_token);
+		}
+		request
+	}
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// response of the videos endpoint, which serializes to the same json it
+/// was deserialized from
+///
+/// ```rust
+/// # use yt_api::videos::Response;
+/// let json = r#"{
+///     "kind": "youtube#videoListResponse",
+///     "etag": "W/\"Dn5xIderbhAnUk5TAW0qkFFir0M\"",
+///     "pageInfo": { "totalResults": 1, "resultsPerPage": 1 },
+///     "items": [{
+///         "kind": "youtube#video",
+///         "etag": "etag",
+///         "id": "DnJgoWDxG2A",
+///         "snippet": {
+///             "publishedAt": "2014-05-15T06:44:14Z",
+///             "channelId": "UCVSVvuqXYbtrHzfqsDw8ITg",
+///             "title": "title",
+///             "thumbnails": { "default": { "url": "https://i.ytimg.com/vi/DnJgoWDxG2A/default.jpg", "width": 120, "height": 90 } },
+///             "liveBroadcastContent": "none"
+///         },
+///         "contentDetails": { "duration": "PT4M13S", "dimension": "2d", "definition": "hd" }
+///     }]
+/// }"#;
+/// let response: Response = serde_json::from_str(json).unwrap();
+///
+/// let serialized = serde_json::to_string(&response).unwrap();
+/// assert_eq!(serde_json::from_str::<Response>(&serialized).unwrap(), response);
+///
+/// let value = serde_json::to_value(&response).unwrap();
+/// assert_eq!(value["items"][0]["contentDetails"]["duration"], "PT4M13S");
+/// assert_eq!(value["pageInfo"]["totalResults"], 1);
+/// assert_eq!(value["etag"], r#"W/"Dn5xIderbhAnUk5TAW0qkFFir0M""#);
+/// ```
+///
+/// Iterating over the response, or a reference to it, yields its videos:
+///
+/// ```rust
+/// # use yt_api::videos::Response;
+/// let response: Response = serde_json::from_str(r#"{
+///     "items": [
+///         { "id": "DnJgoWDxG2A", "contentDetails": { "duration": "PT4M13S" } },
+///         { "id": "dQw4w9WgXcQ", "contentDetails": { "duration": "PT1H2M" } }
+///     ]
+/// }"#).unwrap();
+///
+/// let long = response
+///     .iter()
+///     .filter(|video| {
+///         let duration = video.content_details.duration.as_deref();
+///         duration.map_or(false, |duration| duration.contains('H'))
+///     })
+///     .map(|video| video.id.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(long, ["dQw4w9WgXcQ"]);
+/// assert_eq!(response.len(), 2);
+/// assert_eq!(response.first().unwrap().id, "DnJgoWDxG2A");
+///
+/// for video in &response {
+///     println!("{}", video.id);
+/// }
+/// for video in response {
+///     println!("{}", video.id);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Response {
 	pub kind: String,
+	/// the etag exactly as sent by the api, a weak validator keeps its `W/`
+	/// prefix and quotes
 	pub etag: String,
-    pub next_page_token: Option<String>,
-	pub prev_page_token: Option<String>,	
+	pub next_page_token: Option<String>,
+	pub prev_page_token: Option<String>,
 	pub page_info: PageInfo,
+	/// the found videos, which can be empty even for a successful request,
+	/// e.g. when the requested video was deleted or is private
 	pub items: Vec<VideoResult>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PageInfo {
-	pub total_results: i64,
-	pub results_per_page: i64,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+pagination::page_methods!(Response => VideoResult);
 
+/// video of a [`Response`](struct.Response.html), values and fields unknown
+/// to this crate are accepted
+///
+/// ```rust
+/// # use yt_api::videos::Response;
+/// let response: Response = serde_json::from_str(r#"{
+///     "kind": "youtube#videoListResponseV2",
+///     "items": [{
+///         "kind": "youtube#video",
+///         "id": "DnJgoWDxG2A",
+///         "snippet": { "liveBroadcastContent": "premiere", "newField": 1 },
+///         "newPart": { "value": true }
+///     }]
+/// }"#).unwrap();
+///
+/// let video = &response.items[0];
+/// assert_eq!(video.snippet.live_broadcast_content.as_deref(), Some("premiere"));
+/// #[cfg(feature = "extra-fields")]
+/// assert_eq!(video.extra["newPart"]["value"], true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct VideoResult {
 	pub kind: String,
 	pub etag: String,
-	pub id: String,
+	pub id: VideoId,
 	pub snippet: Snippet,
-    pub content_details: ContentDetails,
+	pub content_details: ContentDetails,
+	pub statistics: Option<Statistics>,
+	pub file_details: Option<FileDetails>,
+	pub processing_details: Option<ProcessingDetails>,
+	pub suggestions: Option<Suggestions>,
+	/// translations of the title and description by language code
+	pub localizations: HashMap<String, Localized>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+impl VideoResult {
+	/// video with the id `id` and everything else left empty, e.g. to build
+	/// the responses of a test
+	///
+	/// The response types implement `Default`, so the parts without a
+	/// `with_` method can be filled in with struct update syntax. The built
+	/// video serializes to the json of the api:
+	///
+	/// ```rust
+	/// # use yt_api::{videos::{Statistics, VideoResult}, Thumbnail, Thumbnails, Timestamp};
+	/// let video = VideoResult::new("DnJgoWDxG2A")
+	///     .with_title("Rust in 100 Seconds")
+	///     .with_description("Rust is a memory-safe compiled programming language")
+	///     .with_channel("UCsBjURrPoezykLs9EqgamOA", "Fireship")
+	///     .with_published_at("2021-09-21T16:00:00Z".parse::<Timestamp>().unwrap())
+	///     .with_thumbnails(Thumbnails {
+	///         high: Some(Thumbnail::new("https://i.ytimg.com/vi/DnJgoWDxG2A/hqdefault.jpg").with_size(480, 360)),
+	///         ..Default::default()
+	///     })
+	///     .with_statistics(Statistics { view_count: Some(String::from("1523")), ..Default::default() });
+	///
+	/// assert_eq!(video.kind, "youtube#video");
+	/// assert_eq!(video.snippet.channel_title.as_deref(), Some("Fireship"));
+	/// assert_eq!(video.snippet.thumbnails.as_ref().unwrap().best().unwrap().width, Some(480));
+	///
+	/// let json = serde_json::to_string(&video).unwrap();
+	/// assert_eq!(serde_json::from_str::<VideoResult>(&json).unwrap(), video);
+	/// ```
+	#[must_use]
+	pub fn new(id: impl Into<VideoId>) -> Self {
+		Self {
+			kind: String::from("youtube#video"),
+			id: id.into(),
+			..Self::default()
+		}
+	}
+
+	#[must_use]
+	pub fn with_title(mut self, title: impl Into<String>) -> Self {
+		self.snippet.title = Some(title.into());
+		self
+	}
+
+	#[must_use]
+	pub fn with_description(mut self, description: impl Into<String>) -> Self {
+		self.snippet.description = Some(description.into());
+		self
+	}
+
+	/// the channel which uploaded the video
+	#[must_use]
+	pub fn with_channel(mut self, id: impl Into<ChannelId>, title: impl Into<String>) -> Self {
+		self.snippet.channel_id = Some(id.into());
+		self.snippet.channel_title = Some(title.into());
+		self
+	}
+
+	#[must_use]
+	pub fn with_published_at(mut self, published_at: Timestamp) -> Self {
+		self.snippet.published_at = Some(published_at);
+		self
+	}
+
+	#[must_use]
+	pub fn with_thumbnails(mut self, thumbnails: Thumbnails) -> Self {
+		self.snippet.thumbnails = Some(thumbnails);
+		self
+	}
+
+	#[must_use]
+	pub fn with_statistics(mut self, statistics: Statistics) -> Self {
+		self.statistics = Some(statistics);
+		self
+	}
+
+	/// url of the watch page of the video, see
+	/// [`VideoId::watch_url`](../struct.VideoId.html#method.watch_url)
+	#[must_use]
+	pub fn watch_url(&self) -> String {
+		self.id.watch_url()
+	}
+
+	/// url of the player for embedding the video into a webpage
+	#[must_use]
+	pub fn embed_url(&self) -> String {
+		self.id.embed_url()
+	}
+
+	/// shortened `youtu.be` url for sharing the video
+	#[must_use]
+	pub fn short_url(&self) -> String {
+		self.id.short_url()
+	}
+
+	/// whether youtube restricts the video to adult viewers, which needs the
+	/// `contentDetails` part
+	///
+	/// ```rust
+	/// # use yt_api::videos::VideoResult;
+	/// let restricted: VideoResult = serde_json::from_str(r#"{
+	///     "id": "DnJgoWDxG2A",
+	///     "contentDetails": { "contentRating": { "ytRating": "ytAgeRestricted" } }
+	/// }"#).unwrap();
+	/// assert!(restricted.is_age_restricted());
+	///
+	/// let rated: VideoResult = serde_json::from_str(r#"{
+	///     "id": "DnJgoWDxG2A",
+	///     "contentDetails": { "contentRating": { "mpaaRating": "mpaaR" } }
+	/// }"#).unwrap();
+	/// assert!(!rated.is_age_restricted());
+	/// assert!(!VideoResult::new("DnJgoWDxG2A").is_age_restricted());
+	/// ```
+	#[must_use]
+	pub fn is_age_restricted(&self) -> bool {
+		self.content_details.yt_rating() == Some("ytAgeRestricted")
+	}
+}
+
+#[cfg(feature = "chrono")]
+impl Published for VideoResult {
+	fn published_at(&self) -> Option<Timestamp> {
+		self.snippet.published_at
+	}
+}
+
+/// one line like `title — channel (4:13) [1234 views]`, leaving out the
+/// parts whose fields were not requested
+///
+/// ```rust
+/// # use yt_api::videos::VideoResult;
+/// let video: VideoResult = serde_json::from_str(r#"{
+///     "id": "DnJgoWDxG2A",
+///     "snippet": { "title": "Rust in 100 Seconds", "channelTitle": "Fireship" },
+///     "contentDetails": { "duration": "PT2M25S" },
+///     "statistics": { "viewCount": "1234567" }
+/// }"#).unwrap();
+/// assert_eq!(video.to_string(), "Rust in 100 Seconds — Fireship (2:25) [1234567 views]");
+///
+/// let live: VideoResult = serde_json::from_str(r#"{
+///     "id": "DnJgoWDxG2A",
+///     "snippet": { "title": "24/7 lofi radio" },
+///     "contentDetails": { "duration": "P1DT2H3M4S" }
+/// }"#).unwrap();
+/// assert_eq!(live.to_string(), "24/7 lofi radio (26:03:04)");
+///
+/// let absurd: VideoResult = serde_json::from_str(r#"{
+///     "id": "DnJgoWDxG2A",
+///     "snippet": { "title": "forever" },
+///     "contentDetails": { "duration": "P99999999999999999W" }
+/// }"#).unwrap();
+/// assert_eq!(absurd.to_string(), "forever (P99999999999999999W)");
+///
+/// let minimal: VideoResult = serde_json::from_str(r#"{ "id": "DnJgoWDxG2A" }"#).unwrap();
+/// assert_eq!(minimal.to_string(), "DnJgoWDxG2A");
+/// ```
+impl Display for VideoResult {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let title = self.snippet.title.as_deref().unwrap_or(self.id.as_str());
+		f.write_str(title)?;
+		if let Some(channel_title) = &self.snippet.channel_title {
+			write!(f, " — {}", channel_title)?;
+		}
+		if let Some(duration) = self.content_details.duration.as_deref() {
+			match parse_duration(duration) {
+				Some(seconds) if seconds >= 3600 => write!(
+					f,
+					" ({}:{:02}:{:02})",
+					seconds / 3600,
+					seconds / 60 % 60,
+					seconds % 60
+				)?,
+				Some(seconds) => write!(f, " ({}:{:02})", seconds / 60, seconds % 60)?,
+				None => write!(f, " ({})", duration)?,
+			}
+		}
+		let view_count = self
+			.statistics
+			.as_ref()
+			.and_then(|statistics| statistics.view_count.as_ref());
+		if let Some(view_count) = view_count {
+			write!(f, " [{} views]", view_count)?;
+		}
+		Ok(())
+	}
+}
+
+/// seconds of an iso 8601 duration like `PT1H2M3S` as used by the api
+fn parse_duration(duration: &str) -> Option<u64> {
+	let mut seconds = 0;
+	let mut number = String::new();
+	let mut in_time = false;
+	for c in duration.strip_prefix('P')?.chars() {
+		let unit = match c {
+			'0'..='9' => {
+				number.push(c);
+				continue;
+			}
+			'T' => {
+				in_time = true;
+				continue;
+			}
+			'W' if !in_time => 7 * 24 * 3600,
+			'D' if !in_time => 24 * 3600,
+			'H' if in_time => 3600,
+			'M' if in_time => 60,
+			'S' if in_time => 1,
+			_ => return None,
+		};
+		let value = number.parse::<u64>().ok()?.checked_mul(unit)?;
+		seconds = value.checked_add(seconds)?;
+		number.clear();
+	}
+	Some(seconds).filter(|_| number.is_empty())
+}
+
+/// snippet of a [`VideoResult`](struct.VideoResult.html)
+///
+/// With the `chrono` feature timestamps are accepted with any number of
+/// fractional digits or none, and as `null`:
+///
+/// ```rust
+/// # #[cfg(feature = "chrono")] {
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use chrono::{DateTime, Utc};
+/// # use yt_api::videos::Snippet;
+/// let published_at = |json: &str| {
+///     let snippet: Snippet = serde_json::from_str(json).unwrap();
+///     snippet.published_at
+/// };
+///
+/// let midnight = UNIX_EPOCH + Duration::from_secs(1_609_459_200);
+/// let midnight = Some(DateTime::<Utc>::from(midnight));
+/// assert_eq!(published_at(r#"{ "publishedAt": "2021-01-01T00:00:00Z" }"#), midnight);
+/// assert_eq!(published_at(r#"{ "publishedAt": "2021-01-01T00:00:00.000Z" }"#), midnight);
+/// assert_eq!(
+///     published_at(r#"{ "publishedAt": "2021-01-01T00:00:00.123456Z" }"#),
+///     midnight.map(|midnight| midnight + chrono::Duration::microseconds(123_456)),
+/// );
+/// assert_eq!(published_at(r#"{ "publishedAt": "2021-01-01T01:00:00+01:00" }"#), midnight);
+/// assert_eq!(published_at(r#"{ "publishedAt": null }"#), None);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Snippet {
-	pub published_at: Option<DateTime<Utc>>,
-	pub channel_id: Option<String>,
+	pub published_at: Option<Timestamp>,
+	pub channel_id: Option<ChannelId>,
 	pub title: Option<String>,
 	pub description: Option<String>,
 	pub thumbnails: Option<Thumbnails>,
 	pub channel_title: Option<String>,
-    pub category_id: Option<String>,
+	pub category_id: Option<String>,
 	pub live_broadcast_content: Option<String>,
+	pub default_language: Option<String>,
+	/// title and description in the language requested with
+	/// [`hl`](struct.Videos.html#method.hl)
+	pub localized: Option<Localized>,
+	/// fields sent by the api which are not modeled by this crate yet
+	#[cfg(feature = "extra-fields")]
+	#[serde(flatten)]
+	pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct Thumbnails {
-	pub default: Option<Thumbnail>,
-	pub medium: Option<Thumbnail>,
-	pub high: Option<Thumbnail>,
-	pub standard: Option<Thumbnail>,
-	pub maxres: Option<Thumbnail>,
-}
+impl Snippet {
+	/// the title with the html entities left in it by the api decoded, see
+	/// [`search::Snippet::description_plain`](../search/struct.Snippet.html#method.description_plain)
+	#[must_use]
+	pub fn title_plain(&self) -> Option<String> {
+		self.title.as_deref().map(common::decode_entities)
+	}
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct Thumbnail {
-	pub url: String,
-	pub width: Option<u64>,
-	pub height: Option<u64>,
+	/// the description with the html entities left in it by the api decoded
+	#[must_use]
+	pub fn description_plain(&self) -> Option<String> {
+		self.description.as_deref().map(common::decode_entities)
+	}
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// content details of a [`VideoResult`](struct.VideoResult.html)
+///
+/// The content rating is kept as sent by the api, it has a key for each of
+/// the dozens of rating systems. The common ones have accessors:
+///
+/// ```rust
+/// # use yt_api::videos::ContentDetails;
+/// let details: ContentDetails = serde_json::from_str(r#"{
+///     "duration": "PT2M25S",
+///     "contentRating": { "ytRating": "ytAgeRestricted", "fskRating": "fsk16" }
+/// }"#).unwrap();
+/// assert_eq!(details.yt_rating(), Some("ytAgeRestricted"));
+/// assert_eq!(details.mpaa_rating(), None);
+/// assert_eq!(details.content_rating.unwrap()["fskRating"], "fsk16");
+///
+/// let unrated: ContentDetails = serde_json::from_str(r#"{ "contentRating": {} }"#).unwrap();
+/// assert_eq!(unrated.yt_rating(), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct ContentDetails {
 	pub duration: Option<String>,
-	pub dimension: Option<String>,	
-    pub definition: Option<String>
+	pub dimension: Option<String>,
+	pub definition: Option<String>,
+	/// ratings of the video by rating system, like
+	/// `{ "mpaaRating": "mpaaPg13" }`
+	pub content_rating: Option<serde_json::Value>,
+}
+
+impl ContentDetails {
+	/// the rating of youtube, `ytAgeRestricted` for age restricted videos
+	#[must_use]
+	pub fn yt_rating(&self) -> Option<&str> {
+		self.rating("ytRating")
+	}
+
+	/// the rating of the motion picture association of america, like
+	/// `mpaaPg13`
+	#[must_use]
+	pub fn mpaa_rating(&self) -> Option<&str> {
+		self.rating("mpaaRating")
+	}
+
+	fn rating(&self, system: &str) -> Option<&str> {
+		self.content_rating.as_ref()?.get(system)?.as_str()
+	}
+}
+
+/// counts of the `statistics` part, the api sends them as strings
+///
+/// Counts hidden by the owner of the video are left out by the api and stay
+/// `None` instead of being reported as zero, the dislike count is only sent
+/// to the owner.
+///
+/// ```rust
+/// # use yt_api::videos::Statistics;
+/// let public: Statistics = serde_json::from_str(r#"{
+///     "viewCount": "1523", "likeCount": "87", "dislikeCount": "3", "favoriteCount": "0", "commentCount": "12"
+/// }"#).unwrap();
+/// assert_eq!(public.like_count.as_deref(), Some("87"));
+/// assert_eq!(public.dislike_count.as_deref(), Some("3"));
+///
+/// let hidden: Statistics = serde_json::from_str(r#"{ "viewCount": "1523", "favoriteCount": "0" }"#).unwrap();
+/// assert_eq!(hidden.view_count.as_deref(), Some("1523"));
+/// assert_eq!(hidden.like_count, None);
+/// assert_eq!(hidden.dislike_count, None);
+/// assert_eq!(hidden.comment_count, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Statistics {
+	pub view_count: Option<String>,
+	pub like_count: Option<String>,
+	pub dislike_count: Option<String>,
+	pub favorite_count: Option<String>,
+	pub comment_count: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDetails {
+	pub file_name: Option<String>,
+	pub file_size: Option<String>,
+	pub file_type: Option<String>,
+	pub container: Option<String>,
+	pub video_streams: Option<Vec<VideoStream>>,
+	pub audio_streams: Option<Vec<AudioStream>>,
+	pub duration_ms: Option<String>,
+	pub bitrate_bps: Option<String>,
+	pub creation_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoStream {
+	pub width_pixels: Option<u32>,
+	pub height_pixels: Option<u32>,
+	pub frame_rate_fps: Option<f64>,
+	pub aspect_ratio: Option<f64>,
+	pub codec: Option<String>,
+	pub bitrate_bps: Option<String>,
+	pub rotation: Option<String>,
+	pub vendor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioStream {
+	pub channel_count: Option<u32>,
+	pub codec: Option<String>,
+	pub bitrate_bps: Option<String>,
+	pub vendor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingDetails {
+	pub processing_status: Option<String>,
+	pub processing_progress: Option<ProcessingProgress>,
+	pub processing_failure_reason: Option<String>,
+	pub file_details_availability: Option<String>,
+	pub processing_issues_availability: Option<String>,
+	pub tag_suggestions_availability: Option<String>,
+	pub editor_suggestions_availability: Option<String>,
+	pub thumbnails_availability: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingProgress {
+	pub parts_total: Option<String>,
+	pub parts_processed: Option<String>,
+	pub time_left_ms: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestions {
+	#[serde(default)]
+	pub processing_errors: Vec<String>,
+	#[serde(default)]
+	pub processing_warnings: Vec<String>,
+	#[serde(default)]
+	pub processing_hints: Vec<String>,
+	#[serde(default)]
+	pub tag_suggestions: Vec<TagSuggestion>,
+	#[serde(default)]
+	pub editor_suggestions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagSuggestion {
+	pub tag: String,
+	#[serde(default)]
+	pub category_restricts: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use futures::StreamExt;
+
+	use crate::{testing::MockTransport, ApiKey, Client, Error};
+
+	fn client(mock: &Arc<MockTransport>) -> Client {
+		Client::with_transport(ApiKey::new("key"), mock.clone())
+	}
+
+	#[test]
+	fn send_raw_keeps_the_json() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"/videos?",
+			200,
+			r#"{ "items": [{ "id": "DnJgoWDxG2A", "newPart": { "value": 1 } }] }"#,
+		);
+
+		let request = client(&mock).videos().id("DnJgoWDxG2A").send_raw();
+		let (response, json) = futures::executor::block_on(request).unwrap();
+		assert_eq!(response.items[0].id, "DnJgoWDxG2A");
+		assert_eq!(json["items"][0]["newPart"]["value"], 1);
+	}
+
+	#[test]
+	fn cloned_request_is_sent_again() {
+		let mock = Arc::new(MockTransport::new());
+		let body =
+			r#"{ "items": [{ "id": "DnJgoWDxG2A", "statistics": { "viewCount": "1523" } }] }"#;
+		mock.expect(
+			"part=snippet%2CcontentDetails%2Cstatistics&id=DnJgoWDxG2A",
+			200,
+			body,
+		)
+		.expect(
+			"part=snippet%2CcontentDetails%2Cstatistics&id=DnJgoWDxG2A",
+			200,
+			body,
+		);
+		let request = client(&mock).videos().id("DnJgoWDxG2A").statistics();
+
+		let (first, second) = futures::executor::block_on(async {
+			let first = request.clone().await.unwrap();
+			(first, request.await.unwrap())
+		});
+		for response in [first, second].iter() {
+			assert_eq!(response.items[0].id, "DnJgoWDxG2A");
+			let statistics = response.items[0].statistics.as_ref().unwrap();
+			assert_eq!(statistics.view_count.as_deref(), Some("1523"));
+		}
+		mock.assert_done();
+	}
+
+	#[test]
+	fn ids_are_joined_with_literal_commas() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, "{}")
+			.expect("/videos?", 200, "{}");
+		let client = client(&mock);
+
+		futures::executor::block_on(async {
+			client
+				.videos()
+				.ids(vec!["DnJgoWDxG2A", "dQw4w9WgXcQ"])
+				.await
+				.unwrap();
+			client.videos().ids(vec!["a&b", "c,d"]).await.unwrap();
+		});
+
+		let requests = mock.requests();
+		// the api does not split at `%2C`
+		assert!(requests[0].url.ends_with("&id=DnJgoWDxG2A,dQw4w9WgXcQ"));
+		// but everything within an id is still encoded
+		assert!(requests[1].url.ends_with("&id=a%26b,c%2Cd"));
+	}
+
+	#[test]
+	fn collect_n_requests_only_the_needed_pages() {
+		let first = r#"{ "nextPageToken": "CAIQAA", "items": [{ "id": "DnJgoWDxG2A" }, { "id": "dQw4w9WgXcQ" }] }"#;
+		let second = r#"{ "items": [{ "id": "kJQP7kiw5Fk" }, { "id": "9bZkp7q19f0" }] }"#;
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, first)
+			.expect("pageToken=CAIQAA", 200, second);
+		let client = client(&mock);
+
+		let request = client.videos().most_popular().max_results(2u8).collect_n(3);
+		let videos = futures::executor::block_on(request).unwrap();
+		let ids = videos
+			.iter()
+			.map(|video| video.id.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(ids, ["DnJgoWDxG2A", "dQw4w9WgXcQ", "kJQP7kiw5Fk"]);
+		mock.assert_done();
+
+		// the first page is enough
+		mock.expect("/videos?", 200, first);
+		let request = client.videos().most_popular().max_results(2u8).collect_n(2);
+		assert_eq!(futures::executor::block_on(request).unwrap().len(), 2);
+		assert_eq!(mock.requests().len(), 3);
+
+		// the chart runs out after two pages
+		mock.expect("/videos?", 200, first)
+			.expect("pageToken=CAIQAA", 200, second);
+		let request = client.videos().most_popular().collect_n(10);
+		assert_eq!(futures::executor::block_on(request).unwrap().len(), 4);
+		assert!(mock.requests()[3].url.contains("&maxResults=10"));
+	}
+
+	#[test]
+	fn batches_report_the_missing_ids() {
+		let mut ids = (0..120)
+			.map(|i| format!("video{:06}", i))
+			.collect::<Vec<_>>();
+		ids[70] = String::from("deleted0001");
+		let mock = Arc::new(MockTransport::new());
+		for chunk in ids.chunks(50) {
+			let items = chunk
+				.iter()
+				.filter(|id| !id.starts_with("deleted"))
+				.map(|id| format!(r#"{{ "id": "{}" }}"#, id))
+				.collect::<Vec<_>>();
+			mock.expect(
+				format!("&id={},", chunk[0]),
+				200,
+				format!(r#"{{ "items": [{}] }}"#, items.join(",")),
+			);
+		}
+
+		let batches =
+			futures::executor::block_on(client(&mock).videos_batch(ids, 2).collect::<Vec<_>>());
+		assert_eq!(batches.len(), 3);
+		let found = batches
+			.iter()
+			.map(|batch| batch.result.as_ref().unwrap().len());
+		assert_eq!(found.sum::<usize>(), 119);
+		let missing = batches
+			.iter()
+			.flat_map(|batch| batch.missing_ids())
+			.collect::<Vec<_>>();
+		assert_eq!(missing, ["deleted0001"]);
+		mock.assert_done();
+	}
+
+	#[test]
+	fn hl_returns_the_localized_snippet() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"part=snippet%2CcontentDetails%2Clocalizations&",
+			200,
+			r#"{ "items": [{
+				"id": "DnJgoWDxG2A",
+				"snippet": {
+					"title": "Rust in 100 Seconds",
+					"defaultLanguage": "en",
+					"localized": { "title": "Rust in 100 Sekunden", "description": "Lerne Rust" }
+				},
+				"localizations": {
+					"de": { "title": "Rust in 100 Sekunden", "description": "Lerne Rust" },
+					"en": { "title": "Rust in 100 Seconds", "description": "Learn Rust" }
+				}
+			}] }"#,
+		);
+
+		let request = client(&mock)
+			.videos()
+			.id("DnJgoWDxG2A")
+			.hl("de")
+			.localizations()
+			.send();
+		let response = futures::executor::block_on(request).unwrap();
+		assert!(mock.requests()[0].url.contains("&hl=de"));
+
+		let video = &response.items[0];
+		assert_eq!(video.snippet.title.as_deref(), Some("Rust in 100 Seconds"));
+		let localized = video.snippet.localized.as_ref().unwrap();
+		assert_eq!(localized.title.as_deref(), Some("Rust in 100 Sekunden"));
+		assert_eq!(
+			video.localizations["en"].description.as_deref(),
+			Some("Learn Rust")
+		);
+	}
+
+	#[test]
+	fn single_resolves_to_the_only_video() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect("/videos?", 200, r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#)
+			.expect("/videos?", 200, r#"{ "items": [] }"#)
+			.expect("/videos?", 200, r#"{ "items": [{}, {}] }"#);
+		let client = client(&mock);
+
+		futures::executor::block_on(async {
+			let video = client.videos().id("DnJgoWDxG2A").single().await.unwrap();
+			assert_eq!(video.unwrap().id, "DnJgoWDxG2A");
+
+			let deleted = client.videos().id("DnJgoWDxG2A").single().await.unwrap();
+			assert!(deleted.is_none());
+
+			let both = client
+				.videos()
+				.ids(vec!["DnJgoWDxG2A", "dQw4w9WgXcQ"])
+				.single()
+				.await;
+			assert!(matches!(both, Err(Error::InvalidParameter { .. })));
+		});
+	}
+
+	#[test]
+	fn send_batch_leaves_out_malformed_ids() {
+		let mock = Arc::new(MockTransport::new());
+		mock.expect(
+			"&id=DnJgoWDxG2A,dQw4w9WgXcQ",
+			200,
+			r#"{ "items": [{ "id": "DnJgoWDxG2A" }] }"#,
+		);
+		let client = client(&mock);
+
+		futures::executor::block_on(async {
+			let ids = vec!["DnJgoWDxG2A", "dQw4w9WgXcQ", "DnJgoWDxG2"];
+			let batch = client.videos().ids(ids).send_batch().await.unwrap();
+			assert_eq!(batch.found[0].id, "DnJgoWDxG2A");
+			assert_eq!(batch.missing, ["dQw4w9WgXcQ"]);
+			assert_eq!(batch.malformed, ["DnJgoWDxG2"]);
+
+			// nothing is sent without any well formed id
+			let batch = client
+				.videos()
+				.ids(vec!["typo"])
+				.send_batch()
+				.await
+				.unwrap();
+			assert!(batch.found.is_empty());
+			assert_eq!(batch.malformed, ["typo"]);
+		});
+		assert_eq!(mock.requests().len(), 1);
+	}
 }
@@ -0,0 +1,55 @@
+//! the api key is shared by its clones, so cloning it for every request of a
+//! client does not allocate
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	cell::Cell,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use yt_api::ApiKey;
+
+/// allocator counting the allocations of the threads which enabled counting,
+/// leaving out those of the test harness running on other threads
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+	static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		if COUNTING.try_with(Cell::get).unwrap_or(false) {
+			ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+		}
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+	let before = ALLOCATIONS.load(Ordering::SeqCst);
+	COUNTING.with(|counting| counting.set(true));
+	let result = f();
+	COUNTING.with(|counting| counting.set(false));
+	(result, ALLOCATIONS.load(Ordering::SeqCst) - before)
+}
+
+#[test]
+fn cloning_the_key_does_not_allocate() {
+	let key = ApiKey::new("your-youtube-api-key");
+
+	let (keys, allocations) =
+		count_allocations(|| (0..1000).map(|_| key.clone()).collect::<Vec<_>>());
+	// only the vector is allocated
+	assert_eq!(allocations, 1);
+	assert_eq!(keys.len(), 1000);
+}
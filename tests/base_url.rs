@@ -0,0 +1,59 @@
+//! a client with its base url pointed at a local http server, sending a real
+//! request over the network instead of going through a mock transport
+#![cfg(feature = "surf")]
+
+use std::{
+	io::{Read, Write},
+	net::TcpListener,
+	thread::{self, JoinHandle},
+};
+
+use yt_api::{ApiKey, Client};
+
+/// answers a single request with a canned videos.list body, returning the
+/// request
+fn serve(listener: TcpListener) -> JoinHandle<String> {
+	thread::spawn(move || {
+		let (mut stream, _) = listener.accept().unwrap();
+		let mut request = [0; 4096];
+		let length = stream.read(&mut request).unwrap();
+		let body = r#"{
+			"kind": "youtube#videoListResponse",
+			"items": [{
+				"kind": "youtube#video",
+				"id": "DnJgoWDxG2A",
+				"snippet": { "title": "mocked" }
+			}]
+		}"#;
+		write!(
+			stream,
+			"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+			body.len(),
+			body,
+		)
+		.unwrap();
+		String::from_utf8_lossy(&request[..length]).into_owned()
+	})
+}
+
+#[test]
+fn videos_are_requested_from_the_base_url() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let address = listener.local_addr().unwrap();
+	let server = serve(listener);
+	let client = Client::new(ApiKey::new("key")).base_url(format!("http://{}/youtube/v3", address));
+
+	let request = client.videos().id("DnJgoWDxG2A");
+	let response = futures::executor::block_on(request.send()).unwrap();
+
+	let request = server.join().unwrap();
+	assert!(
+		request.starts_with("GET /youtube/v3/videos?key=key&"),
+		"{}",
+		request
+	);
+	assert!(request.contains("id=DnJgoWDxG2A"), "{}", request);
+	let video = &response.items[0];
+	assert_eq!(video.id, "DnJgoWDxG2A");
+	assert_eq!(video.snippet.title.as_deref(), Some("mocked"));
+}
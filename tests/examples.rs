@@ -0,0 +1,118 @@
+//! the requests of the examples replayed from cassettes recorded from the
+//! api, so changes to the responses which break the deserialization are
+//! caught without network access
+
+use std::sync::Arc;
+
+use yt_api::{
+	search::{ItemType, VideoLocation},
+	testing::Cassette,
+	ApiKey, Client,
+};
+
+/// client answering the requests with the responses recorded to
+/// `tests/cassettes/<name>.json`
+fn replay(name: &str) -> Client {
+	let path = format!(
+		"{}/tests/cassettes/{}.json",
+		env!("CARGO_MANIFEST_DIR"),
+		name
+	);
+	let cassette = Cassette::replay(&path).unwrap();
+	Client::with_transport(ApiKey::new("key"), Arc::new(cassette))
+}
+
+#[test]
+fn search() {
+	let client = replay("search");
+
+	let request = client.search().q("rust lang").item_type(ItemType::Video);
+	let result = futures::executor::block_on(request.send()).unwrap();
+
+	let first = &result.items[0];
+	assert_eq!(first.snippet.title.as_deref(), Some("Rust in 100 Seconds"));
+	assert_eq!(
+		first.id.video_id.as_ref().unwrap().watch_url(),
+		"https://www.youtube.com/watch?v=5C_HPTJg5ek"
+	);
+	let thumbnails = first.snippet.thumbnails.as_ref().unwrap();
+	assert_eq!(
+		thumbnails.default.as_ref().unwrap().url,
+		"https://i.ytimg.com/vi/5C_HPTJg5ek/default.jpg"
+	);
+	assert_eq!(result.items.len(), 3);
+	assert_eq!(result.next_page_token.as_deref(), Some("CAUQAA"));
+}
+
+#[test]
+fn complex_search() {
+	let client = replay("complex_search");
+
+	let request = client
+		.search()
+		.q("rust lang")
+		.max_results(1)
+		.item_type(ItemType::Video)
+		.location(VideoLocation::new(40.73061, -73.93524))
+		.location_radius("100km")
+		.video_embeddable(true);
+	let result = futures::executor::block_on(request.send()).unwrap();
+
+	assert_eq!(result.items.len(), 1);
+	assert_eq!(
+		result.items[0].snippet.title.as_deref(),
+		Some("Rust in 100 Seconds")
+	);
+	assert_eq!(
+		result.items[0].id.video_id.as_ref().unwrap().watch_url(),
+		"https://www.youtube.com/watch?v=5C_HPTJg5ek"
+	);
+}
+
+#[test]
+fn playlist() {
+	let client = replay("playlist");
+
+	let request = client
+		.playlist_items()
+		.playlist_id("PLVvjrrRCBy2JSHf9tGxGKJ-bYAN_uDCUL")
+		.max_results(50);
+	let result = futures::executor::block_on(request.send()).unwrap();
+
+	let urls = result
+		.items
+		.iter()
+		.map(|item| item.snippet.resource_id.video_id.watch_url())
+		.collect::<Vec<_>>();
+	assert_eq!(
+		urls,
+		[
+			"https://www.youtube.com/watch?v=OX9HJsJUDxA",
+			"https://www.youtube.com/watch?v=6kwFFi_Tzak",
+			"https://www.youtube.com/watch?v=84o-aHdmD8g",
+		]
+	);
+}
+
+#[test]
+fn videos() {
+	let client = replay("videos");
+
+	let request = client.videos().id("DnJgoWDxG2A").single();
+	let video = futures::executor::block_on(request).unwrap().unwrap();
+
+	assert_eq!(
+		video.snippet.title.as_deref(),
+		Some("Why Rust? & What Makes It Different")
+	);
+	assert_eq!(
+		video.watch_url(),
+		"https://www.youtube.com/watch?v=DnJgoWDxG2A"
+	);
+	let thumbnails = video.snippet.thumbnails.as_ref().unwrap();
+	assert_eq!(
+		thumbnails.default.as_ref().unwrap().url,
+		"https://i.ytimg.com/vi/DnJgoWDxG2A/default.jpg"
+	);
+	assert_eq!(video.content_details.duration.as_deref(), Some("PT12M31S"));
+}